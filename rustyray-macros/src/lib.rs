@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use proc_macro::{Literal, TokenStream, TokenTree};
+
+/// Embed every file of a directory into the binary
+///
+/// The path is relative to the crate's `Cargo.toml`. Expands to a
+/// `&'static [(&'static str, &'static [u8])]` of every file, named by its path relative to the
+/// directory with `/` separators, ready for `AssetManager::embed_all`.
+///
+/// **NOTE**: Files added to the directory are only picked up when the invoking crate is rebuilt.
+///
+/// # Examples
+/// ```ignore
+/// use rustyray::prelude::*;
+///
+/// window.assets.embed_all(embed_dir!("assets"));
+/// let texture: Handle<Texture> = window.assets.load(String::from("embedded://wabbit_alpha.png"));
+/// ```
+#[proc_macro]
+pub fn embed_dir(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(tokens) => tokens,
+        Err(message) => format!("compile_error!({message:?})").parse().unwrap(),
+    }
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, String> {
+    let mut tokens = input.into_iter();
+    let (Some(TokenTree::Literal(literal)), None) = (tokens.next(), tokens.next()) else {
+        return Err(String::from("embed_dir! expects a single string literal"));
+    };
+    let dir = parse_str(&literal)?;
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| String::from("embed_dir! needs to be invoked by cargo"))?;
+    let root = Path::new(&manifest_dir).join(&dir);
+
+    let mut files = Vec::new();
+    collect(&root, &mut files).map_err(|err| format!("failed to read {dir}: {err}"))?;
+    files.sort();
+
+    let mut entries = String::new();
+    for file in files {
+        let name = file
+            .strip_prefix(&root)
+            .unwrap()
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let path = file
+            .to_str()
+            .ok_or_else(|| format!("{} is not valid utf-8", file.display()))?;
+        entries.push_str(&format!(
+            "({name:?}, ::core::include_bytes!({path:?}) as &'static [u8]),"
+        ));
+    }
+
+    format!("{{ const FILES: &[(&str, &[u8])] = &[{entries}]; FILES }}")
+        .parse()
+        .map_err(|_| String::from("embed_dir! generated invalid code"))
+}
+
+fn parse_str(literal: &Literal) -> Result<String, String> {
+    let source = literal.to_string();
+    source
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .filter(|s| !s.contains('\\'))
+        .map(String::from)
+        .ok_or_else(|| String::from("embed_dir! expects a plain string literal"))
+}
+
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
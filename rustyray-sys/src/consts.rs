@@ -146,6 +146,195 @@ pub enum MaterialMap {
 pub const MATERIAL_MAP_DIFFUSE: MaterialMap = MaterialMap::Albedo;
 pub const MATERIAL_MAP_SPECULAR: MaterialMap = MaterialMap::Metalness;
 
+/// Pixel formats
+/// - **NOTE**: Support depends on OpenGL version and platform
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 8 bit per pixel (no alpha)
+    UncompressedGrayscale = 1,
+    /// 8*2 bpp (2 channels)
+    UncompressedGrayAlpha,
+    /// 16 bpp
+    UncompressedR5G6B5,
+    /// 24 bpp
+    UncompressedR8G8B8,
+    /// 16 bpp (1 bit alpha)
+    UncompressedR5G5B5A1,
+    /// 16 bpp (4 bit alpha)
+    UncompressedR4G4B4A4,
+    /// 32 bpp
+    UncompressedR8G8B8A8,
+    /// 32 bpp (1 channel - float)
+    UncompressedR32,
+    /// 32*3 bpp (3 channels - float)
+    UncompressedR32G32B32,
+    /// 32*4 bpp (4 channels - float)
+    UncompressedR32G32B32A32,
+    /// 16 bpp (1 channel - half float)
+    UncompressedR16,
+    /// 16*3 bpp (3 channels - half float)
+    UncompressedR16G16B16,
+    /// 16*4 bpp (4 channels - half float)
+    UncompressedR16G16B16A16,
+    /// 4 bpp (no alpha)
+    CompressedDxt1Rgb,
+    /// 4 bpp (1 bit alpha)
+    CompressedDxt1Rgba,
+    /// 8 bpp
+    CompressedDxt3Rgba,
+    /// 8 bpp
+    CompressedDxt5Rgba,
+    /// 4 bpp
+    CompressedEtc1Rgb,
+    /// 4 bpp
+    CompressedEtc2Rgb,
+    /// 8 bpp
+    CompressedEtc2EacRgba,
+    /// 4 bpp
+    CompressedPvrtRgb,
+    /// 4 bpp
+    CompressedPvrtRgba,
+    /// 8 bpp
+    CompressedAstc4x4Rgba,
+    /// 2 bpp
+    CompressedAstc8x8Rgba,
+}
+
+impl PixelFormat {
+    pub fn is_compressed(self) -> bool {
+        self as i32 >= PixelFormat::CompressedDxt1Rgb as i32
+    }
+
+    /// Bits used by one pixel, compressed formats use their average rate
+    pub fn bits_per_pixel(self) -> usize {
+        use PixelFormat::*;
+        match self {
+            UncompressedGrayscale => 8,
+            UncompressedGrayAlpha
+            | UncompressedR5G6B5
+            | UncompressedR5G5B5A1
+            | UncompressedR4G4B4A4
+            | UncompressedR16 => 16,
+            UncompressedR8G8B8 => 24,
+            UncompressedR8G8B8A8 | UncompressedR32 => 32,
+            UncompressedR16G16B16 => 48,
+            UncompressedR16G16B16A16 => 64,
+            UncompressedR32G32B32 => 96,
+            UncompressedR32G32B32A32 => 128,
+            CompressedDxt1Rgb | CompressedDxt1Rgba | CompressedEtc1Rgb | CompressedEtc2Rgb
+            | CompressedPvrtRgb | CompressedPvrtRgba => 4,
+            CompressedDxt3Rgba
+            | CompressedDxt5Rgba
+            | CompressedEtc2EacRgba
+            | CompressedAstc4x4Rgba => 8,
+            CompressedAstc8x8Rgba => 2,
+        }
+    }
+
+    /// Bytes used by one pixel, `None` for compressed formats
+    pub fn bytes_per_pixel(self) -> Option<usize> {
+        (!self.is_compressed()).then(|| self.bits_per_pixel() / 8)
+    }
+
+    /// Bytes used by a `width` x `height` image, same as raylib's `GetPixelDataSize`
+    pub fn data_size(self, width: i32, height: i32) -> usize {
+        use PixelFormat::*;
+        let (width, height) = (width.max(0) as usize, height.max(0) as usize);
+        let size = width * height * self.bits_per_pixel() / 8;
+        // Compressed formats store at least one 4x4 block
+        if width < 4 && height < 4 {
+            match self {
+                CompressedDxt1Rgb | CompressedDxt1Rgba => return 8,
+                CompressedDxt3Rgba | CompressedDxt5Rgba => return 16,
+                _ => {}
+            }
+        }
+        size
+    }
+
+    /// Bytes used by `mipmaps` levels starting at `width` x `height`
+    pub fn mipmaps_data_size(self, width: i32, height: i32, mipmaps: i32) -> usize {
+        let (mut width, mut height) = (width, height);
+        let mut size = 0;
+        for _ in 0..mipmaps.max(1) {
+            size += self.data_size(width, height);
+            width = (width / 2).max(1);
+            height = (height / 2).max(1);
+        }
+        size
+    }
+}
+
+impl TryFrom<i32> for PixelFormat {
+    type Error = i32;
+
+    /// Convert the `format` field of a raw [crate::texture::Image] or [crate::texture::Texture]
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        use PixelFormat::*;
+        const FORMATS: [PixelFormat; 24] = [
+            UncompressedGrayscale,
+            UncompressedGrayAlpha,
+            UncompressedR5G6B5,
+            UncompressedR8G8B8,
+            UncompressedR5G5B5A1,
+            UncompressedR4G4B4A4,
+            UncompressedR8G8B8A8,
+            UncompressedR32,
+            UncompressedR32G32B32,
+            UncompressedR32G32B32A32,
+            UncompressedR16,
+            UncompressedR16G16B16,
+            UncompressedR16G16B16A16,
+            CompressedDxt1Rgb,
+            CompressedDxt1Rgba,
+            CompressedDxt3Rgba,
+            CompressedDxt5Rgba,
+            CompressedEtc1Rgb,
+            CompressedEtc2Rgb,
+            CompressedEtc2EacRgba,
+            CompressedPvrtRgb,
+            CompressedPvrtRgba,
+            CompressedAstc4x4Rgba,
+            CompressedAstc8x8Rgba,
+        ];
+        usize::try_from(value - 1)
+            .ok()
+            .and_then(|index| FORMATS.get(index).copied())
+            .ok_or(value)
+    }
+}
+
+/// Framebuffer attachment points used by [crate::ffi::rl_framebuffer_attach]
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum FramebufferAttachType {
+    ColorChannel0 = 0,
+    ColorChannel1,
+    ColorChannel2,
+    ColorChannel3,
+    ColorChannel4,
+    ColorChannel5,
+    ColorChannel6,
+    ColorChannel7,
+    Depth = 100,
+    Stencil = 200,
+}
+
+/// Kind of object attached by [crate::ffi::rl_framebuffer_attach]
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum FramebufferAttachTextureType {
+    CubemapPositiveX = 0,
+    CubemapNegativeX,
+    CubemapPositiveY,
+    CubemapNegativeY,
+    CubemapPositiveZ,
+    CubemapNegativeZ,
+    Texture2D = 100,
+    RenderBuffer = 200,
+}
+
 /// [crate::texture::Texture] parameters: filter mode
 /// - **NOTE 1**: Filtering considers mipmaps if available in the texture
 /// - **NOTE 2**: Filter is accordingly set for minification and magnification
@@ -192,6 +381,56 @@ pub enum FontType {
     SDF,
 }
 
+/// Shader uniform data type
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ShaderUniformDataType {
+    /// Shader uniform type: float
+    Float,
+    /// Shader uniform type: vec2 (2 float)
+    Vec2,
+    /// Shader uniform type: vec3 (3 float)
+    Vec3,
+    /// Shader uniform type: vec4 (4 float)
+    Vec4,
+    /// Shader uniform type: int
+    Int,
+    /// Shader uniform type: ivec2 (2 int)
+    IVec2,
+    /// Shader uniform type: ivec3 (3 int)
+    IVec3,
+    /// Shader uniform type: ivec4 (4 int)
+    IVec4,
+    /// Shader uniform type: unsigned int
+    UInt,
+    /// Shader uniform type: uivec2 (2 unsigned int)
+    UIVec2,
+    /// Shader uniform type: uivec3 (3 unsigned int)
+    UIVec3,
+    /// Shader uniform type: uivec4 (4 unsigned int)
+    UIVec4,
+    /// Shader uniform type: sampler2d
+    Sampler2D,
+}
+
+/// OpenGL version, as returned by rlGetVersion
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum GlVersion {
+    /// OpenGL 1.1
+    OpenGl11 = 1,
+    /// OpenGL 2.1 (GLSL 120)
+    OpenGl21,
+    /// OpenGL 3.3 (GLSL 330)
+    OpenGl33,
+    /// OpenGL 4.3 (using GLSL 330)
+    OpenGl43,
+    /// OpenGL ES 2.0 (GLSL 100)
+    OpenGlEs20,
+    /// OpenGL ES 3.0 (GLSL 300 es)
+    OpenGlEs30,
+}
+
 /// Color blending modes (pre-defined)
 #[repr(i32)]
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -576,3 +815,26 @@ pub enum ConfigFlag {
     /// Set to try enabling interlaced video format (for V3D)
     InterlacedHint = 0x00010000,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pixel_format_sizes_match_raylib() {
+        use PixelFormat::*;
+        assert_eq!(UncompressedR8G8B8A8.data_size(4, 2), 32);
+        assert_eq!(UncompressedR32G32B32A32.bytes_per_pixel(), Some(16));
+        assert_eq!(CompressedDxt5Rgba.bytes_per_pixel(), None);
+        assert_eq!(CompressedDxt1Rgb.data_size(8, 8), 32);
+        assert_eq!(CompressedDxt1Rgb.data_size(2, 2), 8);
+        assert_eq!(UncompressedGrayscale.mipmaps_data_size(4, 2, 3), 8 + 2 + 1);
+        for format in (1..=24).map(|v| PixelFormat::try_from(v).unwrap()) {
+            assert_eq!(
+                format as i32,
+                PixelFormat::try_from(format as i32).unwrap() as i32
+            );
+            assert!(format.bits_per_pixel() > 0);
+        }
+    }
+}
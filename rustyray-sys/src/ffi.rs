@@ -89,10 +89,12 @@ use crate::{
     camera::Camera2D,
     color::Color,
     consts::{
-        ConfigFlag, GamepadAxis, GamepadButton, Gesture, KeyboardKey, MouseButton, MouseCursor,
-        TextureFilter, TextureWrap,
+        ConfigFlag, FramebufferAttachTextureType, FramebufferAttachType, GamepadAxis,
+        GamepadButton, Gesture, KeyboardKey, MouseButton, MouseCursor, TextureFilter, TextureWrap,
     },
     math::{Rectangle, Vector2},
+    shader::Shader,
+    text::Font,
     texture::{Image, RenderTexture, RenderTexture2D, Texture},
 };
 
@@ -333,6 +335,22 @@ unsafe extern "C" {
         file_data: *const c_uchar,
         data_size: c_int,
     ) -> Image;
+    /// Load image from GPU texture data
+    #[link_name = "LoadImageFromTexture"]
+    pub fn load_image_from_texture(texture: Texture) -> Image;
+    /// Load image from screen buffer (screenshot)
+    #[link_name = "LoadImageFromScreen"]
+    pub fn load_image_from_screen() -> Image;
+    /// Unload image from CPU memory (RAM)
+    #[link_name = "UnloadImage"]
+    pub fn unload_image(image: Image);
+    /// Export image to memory buffer, free it with [mem_free]
+    #[link_name = "ExportImageToMemory"]
+    pub fn export_image_to_memory(
+        image: Image,
+        file_type: *const c_char,
+        data_size: *mut c_int,
+    ) -> *mut c_uchar;
 }
 
 // Texture loading functions
@@ -380,6 +398,52 @@ unsafe extern "C" {
     pub fn set_texture_wrap(texture: Texture, wrap: TextureWrap);
 }
 
+// rlgl: low level textures and framebuffers
+unsafe extern "C" {
+    /// Get current OpenGL version, a [crate::consts::GlVersion]
+    #[link_name = "rlGetVersion"]
+    pub fn rl_get_version() -> c_int;
+    /// Load texture data, `data` may be null to allocate an empty texture
+    #[link_name = "rlLoadTexture"]
+    pub fn rl_load_texture(
+        data: *const c_void,
+        width: c_int,
+        height: c_int,
+        format: c_int,
+        mipmap_count: c_int,
+    ) -> c_uint;
+    /// Load depth texture/renderbuffer (to be attached to fbo)
+    #[link_name = "rlLoadTextureDepth"]
+    pub fn rl_load_texture_depth(width: c_int, height: c_int, use_render_buffer: bool) -> c_uint;
+    /// Unload texture from GPU memory
+    #[link_name = "rlUnloadTexture"]
+    pub fn rl_unload_texture(id: c_uint);
+    /// Load an empty framebuffer
+    #[link_name = "rlLoadFramebuffer"]
+    pub fn rl_load_framebuffer() -> c_uint;
+    /// Attach texture/renderbuffer to a framebuffer
+    #[link_name = "rlFramebufferAttach"]
+    pub fn rl_framebuffer_attach(
+        fbo_id: c_uint,
+        tex_id: c_uint,
+        attach_type: FramebufferAttachType,
+        tex_type: FramebufferAttachTextureType,
+        mip_level: c_int,
+    );
+    /// Verify framebuffer is complete
+    #[link_name = "rlFramebufferComplete"]
+    pub fn rl_framebuffer_complete(id: c_uint) -> bool;
+    /// Delete framebuffer from GPU
+    #[link_name = "rlUnloadFramebuffer"]
+    pub fn rl_unload_framebuffer(id: c_uint);
+    /// Enable render texture (fbo)
+    #[link_name = "rlEnableFramebuffer"]
+    pub fn rl_enable_framebuffer(id: c_uint);
+    /// Disable render texture (fbo), return to default framebuffer
+    #[link_name = "rlDisableFramebuffer"]
+    pub fn rl_disable_framebuffer();
+}
+
 // Texture drawing functions
 unsafe extern "C" {
     /// Draw a [Texture]
@@ -413,6 +477,59 @@ unsafe extern "C" {
     // TODO: Add draw_texture_npatch when NPatchInfo is implemented
 }
 
+// Shader management functions
+unsafe extern "C" {
+    /// Load shader from code strings, NULL uses the default shader for that stage
+    #[link_name = "LoadShaderFromMemory"]
+    pub fn load_shader_from_memory(vs_code: *const c_char, fs_code: *const c_char) -> Shader;
+    /// Check if a shader is valid (loaded on GPU)
+    #[link_name = "IsShaderValid"]
+    pub fn is_shader_valid(shader: Shader) -> bool;
+    /// Get shader uniform location
+    #[link_name = "GetShaderLocation"]
+    pub fn get_shader_location(shader: Shader, uniform_name: *const c_char) -> c_int;
+    /// Set shader uniform value, uniform_type is a ShaderUniformDataType
+    #[link_name = "SetShaderValue"]
+    pub fn set_shader_value(
+        shader: Shader,
+        loc_index: c_int,
+        value: *const c_void,
+        uniform_type: c_int,
+    );
+    /// Unload shader from GPU memory (VRAM)
+    #[link_name = "UnloadShader"]
+    pub fn unload_shader(shader: Shader);
+    /// Begin custom shader drawing
+    #[link_name = "BeginShaderMode"]
+    pub fn begin_shader_mode(shader: Shader);
+    /// End custom shader drawing (use default shader)
+    #[link_name = "EndShaderMode"]
+    pub fn end_shader_mode();
+}
+
+// Font loading/unloading functions
+unsafe extern "C" {
+    /// Get the default Font
+    #[link_name = "GetFontDefault"]
+    pub fn get_font_default() -> Font;
+    /// Load font from memory buffer, fileType refers to extension: i.e. '.ttf'
+    #[link_name = "LoadFontFromMemory"]
+    pub fn load_font_from_memory(
+        file_type: *const c_char,
+        file_data: *const c_uchar,
+        data_size: c_int,
+        font_size: c_int,
+        codepoints: *const c_int,
+        codepoint_count: c_int,
+    ) -> Font;
+    /// Check if a font is valid (font data loaded, WARNING: GPU texture not checked)
+    #[link_name = "IsFontValid"]
+    pub fn is_font_valid(font: Font) -> bool;
+    /// Unload font from GPU memory (VRAM)
+    #[link_name = "UnloadFont"]
+    pub fn unload_font(font: Font);
+}
+
 // Text drawing functions
 unsafe extern "C" {
     /// Draw current FPS
@@ -427,13 +544,55 @@ unsafe extern "C" {
         font_size: c_int,
         color: Color,
     );
+    /// Draw text using font and additional parameters
+    #[link_name = "DrawTextEx"]
+    pub fn draw_text_ex(
+        font: Font,
+        text: *const c_char,
+        position: Vector2,
+        font_size: c_float,
+        spacing: c_float,
+        tint: Color,
+    );
+    /// Draw text using Font and pro parameters (rotation)
+    #[link_name = "DrawTextPro"]
+    pub fn draw_text_pro(
+        font: Font,
+        text: *const c_char,
+        position: Vector2,
+        origin: Vector2,
+        rotation: c_float,
+        font_size: c_float,
+        spacing: c_float,
+        tint: Color,
+    );
+    /// Draw one character (codepoint)
+    #[link_name = "DrawTextCodepoint"]
+    pub fn draw_text_codepoint(
+        font: Font,
+        codepoint: c_int,
+        position: Vector2,
+        font_size: c_float,
+        tint: Color,
+    );
 }
 
 // Text font info functions
 unsafe extern "C" {
+    /// Set vertical line spacing when drawing with line-breaks
+    #[link_name = "SetTextLineSpacing"]
+    pub fn set_text_line_spacing(spacing: c_int);
     /// Measure string width for default font
     #[link_name = "MeasureText"]
     pub fn measure_text(text: *const c_char, font_size: c_int) -> c_int;
+    /// Measure string size for Font
+    #[link_name = "MeasureTextEx"]
+    pub fn measure_text_ex(
+        font: Font,
+        text: *const c_char,
+        font_size: c_float,
+        spacing: c_float,
+    ) -> Vector2;
 }
 
 // Basic shapes drawing functions
@@ -453,6 +612,46 @@ unsafe extern "C" {
     /// Draw line segment cubic-bezier in-out interpolation
     #[link_name = "DrawLineBezier"]
     pub fn draw_line_bezier(start: Vector2, end: Vector2, thick: c_float, color: Color);
+    /// Draw spline: Linear, minimum 2 points
+    #[link_name = "DrawSplineLinear"]
+    pub fn draw_spline_linear(
+        points: *const Vector2,
+        point_count: c_int,
+        thick: c_float,
+        color: Color,
+    );
+    /// Draw spline: B-Spline, minimum 4 points
+    #[link_name = "DrawSplineBasis"]
+    pub fn draw_spline_basis(
+        points: *const Vector2,
+        point_count: c_int,
+        thick: c_float,
+        color: Color,
+    );
+    /// Draw spline: Catmull-Rom, minimum 4 points
+    #[link_name = "DrawSplineCatmullRom"]
+    pub fn draw_spline_catmull_rom(
+        points: *const Vector2,
+        point_count: c_int,
+        thick: c_float,
+        color: Color,
+    );
+    /// Draw spline: Quadratic Bezier, minimum 3 points (1 control point): [p1, c2, p3, c4...]
+    #[link_name = "DrawSplineBezierQuadratic"]
+    pub fn draw_spline_bezier_quadratic(
+        points: *const Vector2,
+        point_count: c_int,
+        thick: c_float,
+        color: Color,
+    );
+    /// Draw spline: Cubic Bezier, minimum 4 points (2 control points): [p1, c2, c3, p4, c5, c6...]
+    #[link_name = "DrawSplineBezierCubic"]
+    pub fn draw_spline_bezier_cubic(
+        points: *const Vector2,
+        point_count: c_int,
+        thick: c_float,
+        color: Color,
+    );
     /// Draw a color-filled circle
     #[link_name = "DrawCircle"]
     pub fn draw_circle(center_x: c_int, center_y: c_int, radius: c_float, color: Color);
@@ -492,6 +691,145 @@ unsafe extern "C" {
     pub fn draw_triangle(v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
     #[link_name = "DrawTriangleLines"]
     pub fn draw_triangle_lines(v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
+    /// Draw a triangle fan defined by points (first vertex is the center)
+    #[link_name = "DrawTriangleFan"]
+    pub fn draw_triangle_fan(points: *const Vector2, point_count: c_int, color: Color);
+    /// Draw a triangle strip defined by points
+    #[link_name = "DrawTriangleStrip"]
+    pub fn draw_triangle_strip(points: *const Vector2, point_count: c_int, color: Color);
+    /// Draw a piece of a circle
+    #[link_name = "DrawCircleSector"]
+    pub fn draw_circle_sector(
+        center: Vector2,
+        radius: c_float,
+        start_angle: c_float,
+        end_angle: c_float,
+        segments: c_int,
+        color: Color,
+    );
+    /// Draw circle sector outline
+    #[link_name = "DrawCircleSectorLines"]
+    pub fn draw_circle_sector_lines(
+        center: Vector2,
+        radius: c_float,
+        start_angle: c_float,
+        end_angle: c_float,
+        segments: c_int,
+        color: Color,
+    );
+    /// Draw ellipse outline
+    #[link_name = "DrawEllipseLines"]
+    pub fn draw_ellipse_lines(
+        center_x: c_int,
+        center_y: c_int,
+        radius_h: c_float,
+        radius_v: c_float,
+        color: Color,
+    );
+    /// Draw ring
+    #[link_name = "DrawRing"]
+    pub fn draw_ring(
+        center: Vector2,
+        inner_radius: c_float,
+        outer_radius: c_float,
+        start_angle: c_float,
+        end_angle: c_float,
+        segments: c_int,
+        color: Color,
+    );
+    /// Draw ring outline
+    #[link_name = "DrawRingLines"]
+    pub fn draw_ring_lines(
+        center: Vector2,
+        inner_radius: c_float,
+        outer_radius: c_float,
+        start_angle: c_float,
+        end_angle: c_float,
+        segments: c_int,
+        color: Color,
+    );
+    /// Draw a vertical-gradient-filled rectangle
+    #[link_name = "DrawRectangleGradientV"]
+    pub fn draw_rectangle_gradient_v(
+        pos_x: c_int,
+        pos_y: c_int,
+        width: c_int,
+        height: c_int,
+        top: Color,
+        bottom: Color,
+    );
+    /// Draw a horizontal-gradient-filled rectangle
+    #[link_name = "DrawRectangleGradientH"]
+    pub fn draw_rectangle_gradient_h(
+        pos_x: c_int,
+        pos_y: c_int,
+        width: c_int,
+        height: c_int,
+        left: Color,
+        right: Color,
+    );
+    /// Draw a gradient-filled rectangle with custom vertex colors
+    #[link_name = "DrawRectangleGradientEx"]
+    pub fn draw_rectangle_gradient_ex(
+        rec: Rectangle,
+        top_left: Color,
+        bottom_left: Color,
+        top_right: Color,
+        bottom_right: Color,
+    );
+    /// Draw rectangle with rounded edges
+    #[link_name = "DrawRectangleRounded"]
+    pub fn draw_rectangle_rounded(
+        rec: Rectangle,
+        roundness: c_float,
+        segments: c_int,
+        color: Color,
+    );
+    /// Draw rectangle lines with rounded edges
+    #[link_name = "DrawRectangleRoundedLines"]
+    pub fn draw_rectangle_rounded_lines(
+        rec: Rectangle,
+        roundness: c_float,
+        segments: c_int,
+        color: Color,
+    );
+    /// Draw rectangle with rounded edges outline
+    #[link_name = "DrawRectangleRoundedLinesEx"]
+    pub fn draw_rectangle_rounded_lines_ex(
+        rec: Rectangle,
+        roundness: c_float,
+        segments: c_int,
+        line_thick: c_float,
+        color: Color,
+    );
+    /// Draw a regular polygon (Vector version)
+    #[link_name = "DrawPoly"]
+    pub fn draw_poly(
+        center: Vector2,
+        sides: c_int,
+        radius: c_float,
+        rotation: c_float,
+        color: Color,
+    );
+    /// Draw a polygon outline of n sides
+    #[link_name = "DrawPolyLines"]
+    pub fn draw_poly_lines(
+        center: Vector2,
+        sides: c_int,
+        radius: c_float,
+        rotation: c_float,
+        color: Color,
+    );
+    /// Draw a polygon outline of n sides with extended parameters
+    #[link_name = "DrawPolyLinesEx"]
+    pub fn draw_poly_lines_ex(
+        center: Vector2,
+        sides: c_int,
+        radius: c_float,
+        rotation: c_float,
+        line_thick: c_float,
+        color: Color,
+    );
 }
 
 // Input-related functions: keyboard
@@ -691,6 +1029,12 @@ unsafe extern "C" {
     /// Open URL with default system browser (if available)
     #[link_name = "OpenURL"]
     pub fn open_url(url: *const c_char);
+    /// Internal memory free, for buffers allocated by raylib
+    #[link_name = "MemFree"]
+    pub fn mem_free(ptr: *mut c_void);
+    /// Internal memory allocator, zeroed, for buffers raylib frees itself
+    #[link_name = "MemAlloc"]
+    pub fn mem_alloc(size: c_uint) -> *mut c_void;
 }
 
 // Audio device management functions
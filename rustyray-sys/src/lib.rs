@@ -4,4 +4,6 @@ pub mod color;
 pub mod consts;
 pub mod ffi;
 pub mod math;
+pub mod shader;
+pub mod text;
 pub mod texture;
@@ -0,0 +1,11 @@
+use std::ffi::{c_int, c_uint};
+
+/// Shader
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct Shader {
+    /// Shader program id
+    pub id: c_uint,
+    /// Shader locations array (RL_MAX_SHADER_LOCATIONS)
+    pub locs: *mut c_int,
+}
@@ -0,0 +1,38 @@
+use std::ffi::c_int;
+
+use crate::math::Rectangle;
+use crate::texture::{Image, Texture};
+
+/// GlyphInfo, font characters glyphs info
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct GlyphInfo {
+    /// Character value (Unicode)
+    pub value: c_int,
+    /// Character offset X when drawing
+    pub offset_x: c_int,
+    /// Character offset Y when drawing
+    pub offset_y: c_int,
+    /// Character advance position X
+    pub advance_x: c_int,
+    /// Character image data
+    pub image: Image,
+}
+
+/// Font, font texture and GlyphInfo array data
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct Font {
+    /// Base size (default chars height)
+    pub base_size: c_int,
+    /// Number of glyph characters
+    pub glyph_count: c_int,
+    /// Padding around the glyph characters
+    pub glyph_padding: c_int,
+    /// Texture atlas containing the glyphs
+    pub texture: Texture,
+    /// Rectangles in texture for the glyphs
+    pub recs: *mut Rectangle,
+    /// Glyphs info data
+    pub glyphs: *mut GlyphInfo,
+}
@@ -4,7 +4,12 @@ use std::fs;
 
 use thiserror::Error;
 
-use crate::ffi::{is_window_ready, load_render_texture, load_texture};
+use crate::consts::{FramebufferAttachTextureType, FramebufferAttachType, PixelFormat};
+use crate::ffi::{
+    is_window_ready, load_render_texture, load_texture, rl_disable_framebuffer,
+    rl_enable_framebuffer, rl_framebuffer_attach, rl_framebuffer_complete, rl_load_framebuffer,
+    rl_load_texture, rl_load_texture_depth, rl_unload_framebuffer, rl_unload_texture,
+};
 
 /// Texture, tex data stored in GPU memory (VRAM)
 #[repr(C)]
@@ -57,6 +62,8 @@ pub enum TextureLoadError {
     FileNotFound(String),
     #[error("you must first create a Window before loading textures")]
     WindowNotReady(),
+    #[error("failed to allocate a {0}x{1} texture")]
+    AllocationFailed(i32, i32),
 }
 
 impl Debug for TextureLoadError {
@@ -81,12 +88,47 @@ impl Texture {
 
         unsafe { Ok(load_texture(CString::new(path).unwrap().as_ptr())) }
     }
+
+    /// Allocate an uninitialized texture with the given `format`
+    pub fn empty(width: i32, height: i32, format: PixelFormat) -> Result<Self, TextureLoadError> {
+        if unsafe { !is_window_ready() } {
+            return Err(TextureLoadError::WindowNotReady());
+        }
+
+        let id = unsafe { rl_load_texture(std::ptr::null(), width, height, format as c_int, 1) };
+        if id == 0 {
+            return Err(TextureLoadError::AllocationFailed(width, height));
+        }
+        Ok(Self {
+            id,
+            width,
+            height,
+            mipmaps: 1,
+            format: format as c_int,
+        })
+    }
+
+    /// The `format` field as a [PixelFormat], the raw value if it is not a known format
+    pub fn pixel_format(&self) -> Result<PixelFormat, c_int> {
+        PixelFormat::try_from(self.format)
+    }
+}
+
+impl Image {
+    /// The `format` field as a [PixelFormat], the raw value if it is not a known format
+    pub fn pixel_format(&self) -> Result<PixelFormat, c_int> {
+        PixelFormat::try_from(self.format)
+    }
 }
 
 #[derive(Error)]
 pub enum RenderTextureLoadError {
     #[error("you must first create a Window before loading textures")]
     WindowNotReady(),
+    #[error("{0:?} can not be used as a render target")]
+    UnsupportedFormat(PixelFormat),
+    #[error("failed to create a complete framebuffer")]
+    FramebufferIncomplete(),
 }
 
 impl Debug for RenderTextureLoadError {
@@ -103,4 +145,72 @@ impl RenderTexture {
 
         unsafe { Ok(load_render_texture(width, height)) }
     }
+
+    /// Same as [RenderTexture::new] with a color buffer of the given `format`, like
+    /// [PixelFormat::UncompressedR32G32B32A32] for HDR rendering
+    pub fn with_format(
+        width: i32,
+        height: i32,
+        format: PixelFormat,
+    ) -> Result<RenderTexture, RenderTextureLoadError> {
+        if unsafe { !is_window_ready() } {
+            return Err(RenderTextureLoadError::WindowNotReady());
+        }
+        if format.is_compressed() {
+            return Err(RenderTextureLoadError::UnsupportedFormat(format));
+        }
+
+        // Mirrors raylib's LoadRenderTexture with a configurable color format
+        unsafe {
+            let id = rl_load_framebuffer();
+            if id == 0 {
+                return Err(RenderTextureLoadError::FramebufferIncomplete());
+            }
+            rl_enable_framebuffer(id);
+            let texture = rl_load_texture(std::ptr::null(), width, height, format as c_int, 1);
+            let depth = rl_load_texture_depth(width, height, true);
+            rl_framebuffer_attach(
+                id,
+                texture,
+                FramebufferAttachType::ColorChannel0,
+                FramebufferAttachTextureType::Texture2D,
+                0,
+            );
+            rl_framebuffer_attach(
+                id,
+                depth,
+                FramebufferAttachType::Depth,
+                FramebufferAttachTextureType::RenderBuffer,
+                0,
+            );
+            let complete = rl_framebuffer_complete(id);
+            rl_disable_framebuffer();
+
+            if !complete {
+                // Unloading the framebuffer also frees its depth renderbuffer
+                rl_unload_texture(texture);
+                rl_unload_framebuffer(id);
+                return Err(RenderTextureLoadError::FramebufferIncomplete());
+            }
+
+            Ok(RenderTexture {
+                id,
+                texture: Texture {
+                    id: texture,
+                    width,
+                    height,
+                    mipmaps: 1,
+                    format: format as c_int,
+                },
+                depth: Texture {
+                    id: depth,
+                    width,
+                    height,
+                    mipmaps: 1,
+                    // Same placeholder raylib uses for its depth renderbuffer
+                    format: 19,
+                },
+            })
+        }
+    }
 }
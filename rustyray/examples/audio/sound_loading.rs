@@ -18,9 +18,15 @@ fn main() {
     let fx_wav: Handle<Sound> = window.assets.load(String::from("assets/audio/sound.wav"));
     let fx_ogg: Handle<Sound> = window.assets.load(String::from("assets/audio/target.ogg"));
 
+    let mut error: Option<String> = None;
+
     while !window.should_close() {
         window.assets.process_assets();
 
+        if let Some(failure) = window.assets.drain_errors::<Sound>().pop() {
+            error = Some(failure.error.to_string());
+        }
+
         let ready = window.assets.is_ready(&fx_wav) && window.assets.is_ready(&fx_ogg);
 
         if ready {
@@ -55,6 +61,15 @@ fn main() {
                     20,
                     Color::DARKGRAY,
                 );
+            } else if let Some(error) = &error {
+                const SIZE: i32 = 20;
+                d.draw_text(
+                    error,
+                    (SCREEN_WIDTH - d.measure_text(error, SIZE)) / 2,
+                    (SCREEN_HEIGHT - SIZE) / 2,
+                    SIZE,
+                    Color::MAROON,
+                );
             } else {
                 const LOADING: &str = "Loading...";
                 const SIZE: i32 = 20;
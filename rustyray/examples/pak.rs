@@ -0,0 +1,40 @@
+use std::process::exit;
+
+use rustyray::prelude::*;
+
+/// Pack a directory into a single archive
+///
+/// `cargo run --example pak -- assets assets.pak`
+///
+/// Mount it with `window.assets.mount_archive(Pak::open("assets.pak")?)` and keep loading
+/// assets with the same paths, e.g. `assets/wabbit_alpha.png`.
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [dir, out] = args.as_slice() else {
+        eprintln!("usage: pak <directory> <output.pak>");
+        exit(1);
+    };
+
+    let result = PakBuilder::new()
+        .add_dir(dir)
+        .and_then(|builder| builder.write(out));
+    if let Err(err) = result {
+        eprintln!("failed to pack {dir}: {err}");
+        exit(1);
+    }
+
+    match Pak::open(out) {
+        Ok(pak) => {
+            let mut names: Vec<&str> = pak.names().collect();
+            names.sort_unstable();
+            for name in names {
+                println!("{name}");
+            }
+            println!("packed {} files into {out}", pak.len());
+        }
+        Err(err) => {
+            eprintln!("failed to read back {out}: {err}");
+            exit(1);
+        }
+    }
+}
@@ -1,22 +1,107 @@
 use std::any::{Any, TypeId};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::io;
 use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::rc::Rc;
 use std::sync::mpsc::{self, Receiver, Sender};
-use std::sync::{Arc, Mutex};
-use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant, SystemTime};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use super::pak::Pak;
+use super::vfs::{AssetSource, Vfs, embedded_name};
+use super::worker::{Flag, WorkerPool};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AssetId(u64);
 
-pub trait Asset: 'static {}
+pub trait Asset: 'static {
+    /// Estimated memory used by the asset in bytes, counted against [AssetManager::set_memory_budget]
+    fn size_estimate(&self) -> usize {
+        0
+    }
+}
+
+/// Handed to [AssetLoader::decode] to read files through the manager's [Vfs]
+#[derive(Debug, Clone, Default)]
+pub struct LoadContext {
+    vfs: Arc<Vfs>,
+}
+
+impl LoadContext {
+    pub fn new(vfs: Vfs) -> Self {
+        Self { vfs: Arc::new(vfs) }
+    }
 
+    pub async fn read(&self, path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+        self.vfs.read(path).await
+    }
+}
+
+/// Assets loaded in the background by [AssetManager::load]
+///
+/// Loading is split in two: [AssetLoader::decode] reads and decodes on a worker thread, then
+/// [AssetLoader::finish] runs on the main thread in [AssetManager::process_assets] for anything
+/// that needs the window or audio device, like uploading to the GPU.
 #[allow(async_fn_in_trait)]
 pub trait AssetLoader: Asset + Sized {
-    type Key: 'static;
-    type Error: std::error::Error + 'static;
+    type Key: Clone + Eq + Hash + Send + 'static;
+    type Error: std::error::Error + Send + 'static;
+    /// CPU-side data handed from [AssetLoader::decode] to [AssetLoader::finish]
+    type Decoded: Send + 'static;
+
+    async fn decode(key: Self::Key, ctx: LoadContext) -> Result<Self::Decoded, Self::Error>;
+
+    fn finish(decoded: Self::Decoded) -> Result<Self, Self::Error>;
+
+    /// File backing `key`, watched for changes when hot reload is enabled
+    fn path(_key: &Self::Key) -> Option<&Path> {
+        None
+    }
+}
+
+/// Called with the current asset and the freshly reloaded one, returning `None` vetoes the reload
+pub type ReloadHook<T> = dyn FnMut(&T, T) -> Option<T>;
+
+#[derive(Debug, Clone)]
+pub enum LoadState {
+    Pending,
+    Loaded,
+    Failed(Rc<dyn std::error::Error>),
+}
+
+impl LoadState {
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Pending)
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        matches!(self, Self::Loaded)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+}
+
+/// A failed [AssetManager::load], drained with [AssetManager::drain_errors]
+#[derive(Debug)]
+pub struct LoadError<T: AssetLoader> {
+    pub id: AssetId,
+    pub error: Rc<T::Error>,
+}
 
-    async fn load(key: Self::Key) -> Result<Self, Self::Error>;
+impl<T: AssetLoader> Clone for LoadError<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            error: Rc::clone(&self.error),
+        }
+    }
 }
 
 pub trait SyncAsset: Asset + Sized {
@@ -62,16 +147,134 @@ impl<T: Asset> Handle<T> {
     pub fn id(&self) -> AssetId {
         self.rc.id
     }
+
+    pub fn downgrade(&self) -> WeakHandle<T> {
+        WeakHandle {
+            rc: Arc::downgrade(&self.rc),
+            id: self.rc.id,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Number of strong handles, including those held by pins and groups
+    pub fn ref_count(&self) -> usize {
+        Arc::strong_count(&self.rc)
+    }
+}
+
+/// A [Handle] that does not keep its asset alive
+#[derive(Debug)]
+pub struct WeakHandle<T: Asset> {
+    rc: Weak<RefCount>,
+    id: AssetId,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Asset> Clone for WeakHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            rc: Weak::clone(&self.rc),
+            id: self.id,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset> WeakHandle<T> {
+    pub fn id(&self) -> AssetId {
+        self.id
+    }
+
+    /// Get a strong [Handle] back, `None` once every strong handle was dropped
+    pub fn upgrade(&self) -> Option<Handle<T>> {
+        Some(Handle {
+            rc: self.rc.upgrade()?,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.rc.strong_count() > 0
+    }
+}
+
+/// A live asset listed by [AssetManager::live_assets]
+#[derive(Debug, Clone)]
+pub struct LiveAsset {
+    pub id: AssetId,
+    /// Strong handles, including those held by pins and groups
+    pub ref_count: usize,
+    pub pinned: bool,
+    pub state: LoadState,
+}
+
+/// Change to an asset of type `T`, drained with [AssetManager::drain_events]
+#[derive(Debug)]
+pub enum AssetEvent<T: Asset> {
+    /// Inserted, created or finished loading
+    Added(WeakHandle<T>),
+    /// Replaced in place, e.g. by a hot reload
+    Modified(WeakHandle<T>),
+    /// Released after its last handle was dropped
+    Removed(WeakHandle<T>),
+    /// A load or reload failed, a failed reload keeps the previous asset
+    Failed(WeakHandle<T>, Rc<dyn std::error::Error>),
+}
+
+impl<T: Asset> Clone for AssetEvent<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Added(handle) => Self::Added(handle.clone()),
+            Self::Modified(handle) => Self::Modified(handle.clone()),
+            Self::Removed(handle) => Self::Removed(handle.clone()),
+            Self::Failed(handle, error) => Self::Failed(handle.clone(), Rc::clone(error)),
+        }
+    }
+}
+
+impl<T: Asset> AssetEvent<T> {
+    pub fn handle(&self) -> &WeakHandle<T> {
+        match self {
+            Self::Added(handle)
+            | Self::Modified(handle)
+            | Self::Removed(handle)
+            | Self::Failed(handle, _) => handle,
+        }
+    }
+
+    pub fn id(&self) -> AssetId {
+        self.handle().id()
+    }
 }
 
 struct Store<T: Asset> {
     assets: HashMap<AssetId, T>,
+    failed: HashMap<AssetId, Rc<dyn std::error::Error>>,
+    refs: HashMap<AssetId, Weak<RefCount>>,
+    /// Events since the last [AssetManager::process_assets]
+    staged: Vec<AssetEvent<T>>,
+    /// Events published by the last [AssetManager::process_assets]
+    events: Vec<AssetEvent<T>>,
+}
+
+impl<T: Asset> Store<T> {
+    fn weak(&self, id: AssetId) -> WeakHandle<T> {
+        WeakHandle {
+            rc: self.refs.get(&id).cloned().unwrap_or_default(),
+            id,
+            _marker: PhantomData,
+        }
+    }
 }
 
 trait ErasedStore {
     fn as_any_ref(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
     fn remove(&mut self, id: AssetId);
+    fn load_state(&self, id: AssetId) -> LoadState;
+    fn publish_events(&mut self);
+    fn size_of(&self, id: AssetId) -> usize;
+    fn usage(&self) -> usize;
 }
 
 impl<T: Asset> ErasedStore for Store<T> {
@@ -84,7 +287,103 @@ impl<T: Asset> ErasedStore for Store<T> {
     }
 
     fn remove(&mut self, id: AssetId) {
+        let handle = self.weak(id);
         self.assets.remove(&id);
+        self.failed.remove(&id);
+        if self.refs.remove(&id).is_some() {
+            self.staged.push(AssetEvent::Removed(handle));
+        }
+    }
+
+    fn publish_events(&mut self) {
+        self.events = std::mem::take(&mut self.staged);
+    }
+
+    fn size_of(&self, id: AssetId) -> usize {
+        self.assets.get(&id).map_or(0, Asset::size_estimate)
+    }
+
+    fn usage(&self) -> usize {
+        self.assets.values().map(Asset::size_estimate).sum()
+    }
+
+    fn load_state(&self, id: AssetId) -> LoadState {
+        if self.assets.contains_key(&id) {
+            LoadState::Loaded
+        } else if let Some(error) = self.failed.get(&id) {
+            LoadState::Failed(Rc::clone(error))
+        } else {
+            LoadState::Pending
+        }
+    }
+}
+
+struct LoaderState<T: AssetLoader> {
+    reload_hook: Option<Box<ReloadHook<T>>>,
+    errors: Vec<LoadError<T>>,
+    by_key: HashMap<T::Key, (AssetId, Weak<RefCount>)>,
+    keys: HashMap<AssetId, T::Key>,
+    /// Loaded assets without handles kept for a memory budget, with the tick they were released at
+    unused: HashMap<AssetId, u64>,
+}
+
+impl<T: AssetLoader> Default for LoaderState<T> {
+    fn default() -> Self {
+        Self {
+            reload_hook: None,
+            errors: Vec::new(),
+            by_key: HashMap::new(),
+            keys: HashMap::new(),
+            unused: HashMap::new(),
+        }
+    }
+}
+
+trait ErasedLoader {
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn release(&mut self, id: AssetId);
+    /// Keep a released asset around until evicted, returns whether it can be revived by its key
+    fn keep_unused(&mut self, id: AssetId, tick: u64) -> bool;
+    fn oldest_unused(&self) -> Option<(u64, AssetId)>;
+}
+
+impl<T: AssetLoader> ErasedLoader for LoaderState<T> {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn release(&mut self, id: AssetId) {
+        self.unused.remove(&id);
+        let Some(key) = self.keys.remove(&id) else {
+            return;
+        };
+        // The key may already point at a newer load if it was requested again before this release
+        if self
+            .by_key
+            .get(&key)
+            .is_some_and(|(cached, _)| *cached == id)
+        {
+            self.by_key.remove(&key);
+        }
+    }
+
+    fn keep_unused(&mut self, id: AssetId, tick: u64) -> bool {
+        let revivable = self
+            .keys
+            .get(&id)
+            .and_then(|key| self.by_key.get(key))
+            .is_some_and(|(cached, _)| *cached == id);
+        if revivable {
+            self.unused.insert(id, tick);
+        }
+        revivable
+    }
+
+    fn oldest_unused(&self) -> Option<(u64, AssetId)> {
+        self.unused
+            .iter()
+            .map(|(&id, &tick)| (tick, id))
+            .min_by_key(|(tick, _)| *tick)
     }
 }
 
@@ -96,14 +395,192 @@ type LoadPollingFn = dyn FnMut(&mut AssetManager, &mut Context<'_>) -> Poll<()>;
 
 struct PendingLoad {
     poll: Box<LoadPollingFn>,
+    flag: Arc<Flag>,
+}
+
+/// Loaded, failed and total member counts of an [AssetGroup]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GroupProgress {
+    pub total: usize,
+    pub loaded: usize,
+    pub failed: usize,
+}
+
+impl GroupProgress {
+    /// Members that finished loading, successfully or not
+    pub fn finished(&self) -> usize {
+        self.loaded + self.failed
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.finished() == self.total
+    }
+
+    /// Finished fraction between `0.0` and `1.0`, an empty group is complete
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.finished() as f32 / self.total as f32
+        }
+    }
+}
+
+struct GroupMember {
+    type_id: TypeId,
+    id: AssetId,
+    state: LoadState,
+    _handle: Box<dyn Any>,
+}
+
+#[derive(Default)]
+struct GroupShared {
+    members: RefCell<Vec<GroupMember>>,
+    progress: Cell<GroupProgress>,
+    waker: Cell<Option<Waker>>,
+}
+
+/// A batch of handles of any asset type tracked together, e.g. for a loading screen
+///
+/// Members are kept alive by the group. Progress is refreshed on every
+/// [AssetManager::process_assets], either poll [AssetGroup::progress] each frame or await
+/// [AssetGroup::finished].
+///
+/// # Examples
+/// ```no_run
+/// use rustyray::prelude::*;
+///
+/// let mut window = WindowBuilder::new(800, 600, "Example").build().unwrap();
+/// let mut group = window.assets.group();
+/// group.add(&window.assets.load::<Texture>(String::from("assets/wabbit_alpha.png")));
+/// group.add(&window.assets.load::<Sound>(String::from("assets/audio/sound.wav")));
+///
+/// while !window.should_close() {
+///     window.assets.process_assets();
+///     let progress = group.progress();
+///     println!("{} of {} assets loaded", progress.finished(), progress.total);
+///     if progress.is_done() {
+///         break;
+///     }
+/// }
+/// ```
+pub struct AssetGroup {
+    shared: Rc<GroupShared>,
+}
+
+impl std::fmt::Debug for AssetGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AssetGroup")
+            .field("progress", &self.progress())
+            .finish_non_exhaustive()
+    }
+}
+
+impl AssetGroup {
+    /// Track `handle`, adding an asset already in the group does nothing
+    pub fn add<T: Asset>(&mut self, handle: &Handle<T>) {
+        let mut members = self.shared.members.borrow_mut();
+        if members.iter().any(|member| member.id == handle.id()) {
+            return;
+        }
+        members.push(GroupMember {
+            type_id: TypeId::of::<T>(),
+            id: handle.id(),
+            state: LoadState::Pending,
+            _handle: Box::new(handle.clone()),
+        });
+
+        let mut progress = self.shared.progress.get();
+        progress.total = members.len();
+        self.shared.progress.set(progress);
+    }
+
+    pub fn with<T: Asset>(mut self, handle: &Handle<T>) -> Self {
+        self.add(handle);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.members.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Progress as of the last [AssetManager::process_assets]
+    pub fn progress(&self) -> GroupProgress {
+        self.shared.progress.get()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.progress().is_done()
+    }
+
+    /// Members that failed to load with their error
+    pub fn failures(&self) -> Vec<(AssetId, Rc<dyn std::error::Error>)> {
+        self.shared
+            .members
+            .borrow()
+            .iter()
+            .filter_map(|member| match &member.state {
+                LoadState::Failed(error) => Some((member.id, Rc::clone(error))),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Resolves once every member finished loading, driven by [AssetManager::process_assets]
+    pub fn finished(&self) -> GroupFinished<'_> {
+        GroupFinished { group: self }
+    }
+}
+
+/// Future returned by [AssetGroup::finished]
+#[derive(Debug)]
+pub struct GroupFinished<'a> {
+    group: &'a AssetGroup,
+}
+
+impl Future for GroupFinished<'_> {
+    type Output = GroupProgress;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let progress = self.group.progress();
+        if progress.is_done() {
+            return Poll::Ready(progress);
+        }
+        self.group.shared.waker.set(Some(cx.waker().clone()));
+        Poll::Pending
+    }
 }
 
+struct Watch {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    alive: Weak<RefCount>,
+    reload: Box<dyn Fn(&mut AssetManager)>,
+}
+
+const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
 pub struct AssetManager {
     next_id: Cell<u64>,
     stores: HashMap<TypeId, Box<dyn ErasedStore>>,
     jobs_rx: Mutex<Receiver<Job>>,
     jobs_tx: Sender<Job>,
     pending: Vec<PendingLoad>,
+    hot_reload: bool,
+    watch_interval: Duration,
+    last_watch: Option<Instant>,
+    watches: Vec<Watch>,
+    loaders: HashMap<TypeId, Box<dyn ErasedLoader>>,
+    context: LoadContext,
+    workers: Option<WorkerPool>,
+    groups: Vec<std::rc::Weak<GroupShared>>,
+    pinned: HashMap<AssetId, Box<dyn Any>>,
+    budget: Option<usize>,
+    release_tick: u64,
 }
 
 impl std::fmt::Debug for AssetManager {
@@ -112,16 +589,6 @@ impl std::fmt::Debug for AssetManager {
     }
 }
 
-fn noop_waker() -> Waker {
-    const VTABLE: RawWakerVTable = RawWakerVTable::new(
-        |_| RawWaker::new(std::ptr::null(), &VTABLE),
-        |_| {},
-        |_| {},
-        |_| {},
-    );
-    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
-}
-
 impl Default for AssetManager {
     fn default() -> Self {
         Self::new()
@@ -138,6 +605,17 @@ impl AssetManager {
             jobs_rx: Mutex::new(jobs_rx),
             jobs_tx,
             pending: Vec::new(),
+            hot_reload: false,
+            watch_interval: DEFAULT_WATCH_INTERVAL,
+            last_watch: None,
+            watches: Vec::new(),
+            loaders: HashMap::new(),
+            context: LoadContext::default(),
+            workers: None,
+            groups: Vec::new(),
+            pinned: HashMap::new(),
+            budget: None,
+            release_tick: 0,
         }
     }
 
@@ -147,16 +625,25 @@ impl AssetManager {
         AssetId(id)
     }
 
-    fn make_handle<T: Asset>(&self, id: AssetId) -> Handle<T> {
+    fn make_handle<T: Asset>(&mut self, id: AssetId) -> Handle<T> {
+        let rc = Arc::new(RefCount {
+            id,
+            jobs: self.jobs_tx.clone(),
+        });
+        self.store_mut::<T>().refs.insert(id, Arc::downgrade(&rc));
         Handle {
-            rc: Arc::new(RefCount {
-                id,
-                jobs: self.jobs_tx.clone(),
-            }),
+            rc,
             _marker: PhantomData,
         }
     }
 
+    /// Handle to an asset that is never loaded, for tests that only need an id
+    #[cfg(test)]
+    pub(crate) fn dummy_handle<T: Asset>(&mut self) -> Handle<T> {
+        let id = self.alloc_id();
+        self.make_handle(id)
+    }
+
     fn store<T: Asset>(&self) -> Option<&Store<T>> {
         self.stores
             .get(&TypeId::of::<T>())?
@@ -169,6 +656,10 @@ impl AssetManager {
         self.stores.entry(type_id).or_insert_with(|| {
             Box::new(Store::<T> {
                 assets: HashMap::new(),
+                failed: HashMap::new(),
+                refs: HashMap::new(),
+                staged: Vec::new(),
+                events: Vec::new(),
             })
         });
         self.stores
@@ -179,77 +670,383 @@ impl AssetManager {
             .unwrap()
     }
 
+    fn loader_mut<T: AssetLoader>(&mut self) -> &mut LoaderState<T> {
+        self.loaders
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(LoaderState::<T>::default()))
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
+    }
+
     fn insert_at<T: Asset>(&mut self, id: AssetId, asset: T) {
-        self.store_mut::<T>().assets.insert(id, asset);
+        let store = self.store_mut::<T>();
+        store.failed.remove(&id);
+        let event = match store.assets.insert(id, asset) {
+            Some(_) => AssetEvent::Modified(store.weak(id)),
+            None => AssetEvent::Added(store.weak(id)),
+        };
+        store.staged.push(event);
+    }
+
+    fn fail_at<T: AssetLoader>(&mut self, id: AssetId, error: T::Error) {
+        let error = Rc::new(error);
+        self.mark_failed::<T>(id, error.clone());
+        self.loader_mut::<T>().errors.push(LoadError { id, error });
+    }
+
+    /// Fail the load without a [LoadError], for errors that are not a `T::Error`
+    fn mark_failed<T: Asset>(&mut self, id: AssetId, error: Rc<dyn std::error::Error>) {
+        let store = self.store_mut::<T>();
+        if !store.assets.contains_key(&id) {
+            store.failed.insert(id, error.clone());
+        }
+        let handle = store.weak(id);
+        store.staged.push(AssetEvent::Failed(handle, error));
+    }
+
+    /// The last handle to an asset was dropped, keep it cached if a memory budget is set
+    fn release(&mut self, type_id: TypeId, id: AssetId) {
+        if self.budget.is_some()
+            && self.erased_state(type_id, id).is_loaded()
+            && let Some(loader) = self.loaders.get_mut(&type_id)
+            && loader.keep_unused(id, self.release_tick)
+        {
+            self.release_tick += 1;
+            return;
+        }
+        self.remove(type_id, id);
+    }
+
+    /// Evict least recently released assets until the usage fits in `budget` or none are left
+    fn evict_unused(&mut self, budget: usize) {
+        let mut usage = self.memory_usage();
+        while usage > budget {
+            let Some((_, type_id, id)) = self
+                .loaders
+                .iter()
+                .filter_map(|(type_id, loader)| {
+                    let (tick, id) = loader.oldest_unused()?;
+                    Some((tick, *type_id, id))
+                })
+                .min_by_key(|(tick, ..)| *tick)
+            else {
+                return;
+            };
+            usage -= self
+                .stores
+                .get(&type_id)
+                .map_or(0, |store| store.size_of(id));
+            self.remove(type_id, id);
+        }
+    }
+
+    /// Keep loaded assets cached after their last handle is dropped, while the estimated memory
+    /// usage fits in `budget` bytes
+    ///
+    /// Cached assets are handed out again by [AssetManager::load] with the same key, the least
+    /// recently released ones are evicted first and transparently loaded again when requested.
+    /// Assets with a live handle are never evicted. `None` disables caching and evicts every
+    /// unreferenced asset.
+    pub fn set_memory_budget(&mut self, budget: Option<usize>) {
+        self.budget = budget;
+        self.evict_unused(budget.unwrap_or(0));
+    }
+
+    pub fn memory_budget(&self) -> Option<usize> {
+        self.budget
+    }
+
+    /// Sum of [Asset::size_estimate] of every loaded asset, cached ones included
+    pub fn memory_usage(&self) -> usize {
+        self.stores.values().map(|store| store.usage()).sum()
     }
 
     fn remove(&mut self, type_id: TypeId, id: AssetId) {
+        if let Some(loader) = self.loaders.get_mut(&type_id) {
+            loader.release(id);
+        }
         let Some(store) = self.stores.get_mut(&type_id) else {
             return;
         };
         store.remove(id);
     }
 
+    fn cached<T: AssetLoader>(&mut self, key: &T::Key) -> Option<Handle<T>> {
+        let loader = self.loader_mut::<T>();
+        let (id, alive) = loader.by_key.get(key)?;
+        let (id, alive) = (*id, alive.upgrade());
+        let Some(rc) = alive else {
+            return self.revive::<T>(id, key);
+        };
+        if self
+            .store::<T>()
+            .is_some_and(|store| store.failed.contains_key(&id))
+        {
+            return None;
+        }
+        Some(Handle {
+            rc,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Hand out a new handle to an unreferenced asset kept for the memory budget
+    fn revive<T: AssetLoader>(&mut self, id: AssetId, key: &T::Key) -> Option<Handle<T>> {
+        self.loader_mut::<T>().unused.remove(&id)?;
+        let handle = self.make_handle::<T>(id);
+        let alive = Arc::downgrade(&handle.rc);
+        self.loader_mut::<T>()
+            .by_key
+            .insert(key.clone(), (id, alive.clone()));
+        self.watch::<T>(id, key, &alive);
+        Some(handle)
+    }
+
+    fn watch<T: AssetLoader>(&mut self, id: AssetId, key: &T::Key, alive: &Weak<RefCount>) {
+        let Some(path) = T::path(key) else {
+            return;
+        };
+        if embedded_name(path).is_some() {
+            return;
+        }
+        self.watches.push(Watch {
+            path: path.to_path_buf(),
+            // Seeded now so an edit before the first check still counts as a change
+            modified: self.context.vfs.modified(path),
+            alive: alive.clone(),
+            reload: Box::new({
+                let key = key.clone();
+                let alive = alive.clone();
+                move |manager: &mut AssetManager| {
+                    manager.reload::<T>(id, key.clone(), alive.clone())
+                }
+            }),
+        });
+    }
+
     pub fn insert<T: Asset>(&mut self, asset: T) -> Handle<T> {
         let id = self.alloc_id();
+        let handle = self.make_handle(id);
         self.insert_at(id, asset);
-        self.make_handle(id)
+        handle
     }
 
-    pub fn create<T: SyncAsset>(&mut self, key: T::Key) -> Result<Handle<T>, T::Error> {
-        Ok(self.insert(T::create(key)?))
+    pub fn create<T: SyncAsset>(&mut self, key: impl Into<T::Key>) -> Result<Handle<T>, T::Error> {
+        Ok(self.insert(T::create(key.into())?))
     }
 
-    pub fn load<T: Asset + AssetLoader>(&mut self, key: T::Key) -> Handle<T> {
+    /// Load `T` from `key`, sharing any in-flight, loaded or budget-cached asset with the same key
+    pub fn load<T: Asset + AssetLoader>(&mut self, key: impl Into<T::Key>) -> Handle<T> {
+        let key = key.into();
+        if let Some(handle) = self.cached::<T>(&key) {
+            return handle;
+        }
+
         let id = self.alloc_id();
         let handle = self.make_handle::<T>(id);
         let alive = Arc::downgrade(&handle.rc);
 
-        let mut future = Box::pin(T::load(key));
+        let loader = self.loader_mut::<T>();
+        loader.by_key.insert(key.clone(), (id, alive.clone()));
+        loader.keys.insert(id, key.clone());
+
+        self.watch::<T>(id, &key, &alive);
+        self.spawn_load::<T>(id, key, alive, false);
+
+        handle
+    }
+
+    fn reload<T: AssetLoader>(&mut self, id: AssetId, key: T::Key, alive: Weak<RefCount>) {
+        self.spawn_load::<T>(id, key, alive, true);
+    }
+
+    fn spawn_load<T: AssetLoader>(
+        &mut self,
+        id: AssetId,
+        key: T::Key,
+        alive: Weak<RefCount>,
+        reload: bool,
+    ) {
+        let ctx = self.context.clone();
+        let mut task = self
+            .workers
+            .get_or_insert_with(WorkerPool::new)
+            .spawn(move || T::decode(key, ctx));
 
         self.pending.push(PendingLoad {
             poll: Box::new(move |manager: &mut AssetManager, cx: &mut Context<'_>| {
-                match future.as_mut().poll(cx) {
-                    Poll::Ready(Ok(asset)) => {
-                        if alive.upgrade().is_some() {
-                            manager.insert_at(id, asset);
-                        }
-                        Poll::Ready(())
-                    }
-                    Poll::Ready(Err(err)) => {
-                        eprintln!("failed to load asset {id:?}: {err}");
-                        Poll::Ready(())
+                let Poll::Ready(result) = Pin::new(&mut task).poll(cx) else {
+                    return Poll::Pending;
+                };
+                if alive.upgrade().is_none() {
+                    return Poll::Ready(());
+                }
+                let decoded = match result {
+                    Ok(decoded) => decoded,
+                    Err(panicked) => {
+                        manager.mark_failed::<T>(id, Rc::new(panicked));
+                        return Poll::Ready(());
                     }
-                    Poll::Pending => Poll::Pending,
+                };
+                match decoded.and_then(T::finish) {
+                    Ok(asset) if reload => manager.replace_at(id, asset),
+                    Ok(asset) => manager.insert_at(id, asset),
+                    Err(err) => manager.fail_at::<T>(id, err),
                 }
+                Poll::Ready(())
             }),
+            flag: Flag::new(),
         });
+    }
 
-        handle
+    fn replace_at<T: AssetLoader>(&mut self, id: AssetId, asset: T) {
+        let type_id = TypeId::of::<T>();
+        let hook = self
+            .loaders
+            .get_mut(&type_id)
+            .and_then(|loader| loader.as_any_mut().downcast_mut::<LoaderState<T>>())
+            .and_then(|loader| loader.reload_hook.as_mut());
+        let current = self
+            .stores
+            .get(&type_id)
+            .and_then(|store| store.as_any_ref().downcast_ref::<Store<T>>())
+            .and_then(|store| store.assets.get(&id));
+
+        let asset = match (hook, current) {
+            (Some(hook), Some(current)) => hook(current, asset),
+            _ => Some(asset),
+        };
+        if let Some(asset) = asset {
+            self.insert_at(id, asset);
+        }
+    }
+
+    pub fn vfs(&self) -> &Vfs {
+        &self.context.vfs
+    }
+
+    /// Changes only apply to loads started afterwards
+    pub fn vfs_mut(&mut self) -> &mut Vfs {
+        Arc::make_mut(&mut self.context.vfs)
+    }
+
+    /// Mount `source` on the [Vfs], see [Vfs::mount]
+    pub fn mount(&mut self, source: impl AssetSource, priority: i32) {
+        self.vfs_mut().mount(source, priority);
+    }
+
+    pub fn unmount_all(&mut self) {
+        self.vfs_mut().unmount_all();
+    }
+
+    /// Read every asset loaded from now on out of `archive` instead of any mounted source
+    pub fn mount_archive(&mut self, archive: Pak) {
+        let vfs = self.vfs_mut();
+        vfs.unmount_all();
+        vfs.mount(archive, 0);
+    }
+
+    /// Register `bytes` to be loaded from `embedded://{name}`, e.g. the output of [include_bytes]
+    pub fn embed(&mut self, name: impl Into<String>, bytes: &'static [u8]) {
+        self.vfs_mut().embed(name, bytes);
+    }
+
+    /// Register every file embedded with [super::vfs::embed_dir]
+    pub fn embed_all(&mut self, files: &[(&str, &'static [u8])]) {
+        let vfs = self.vfs_mut();
+        for (name, bytes) in files {
+            vfs.embed(*name, bytes);
+        }
+    }
+
+    /// Reload assets loaded from a file whenever the file changes on disk
+    pub fn set_hot_reload(&mut self, enabled: bool) {
+        self.hot_reload = enabled;
+        self.last_watch = None;
+    }
+
+    pub fn is_hot_reload(&self) -> bool {
+        self.hot_reload
+    }
+
+    /// How often watched files are checked for changes, defaults to 500ms
+    pub fn set_watch_interval(&mut self, interval: Duration) {
+        self.watch_interval = interval;
+    }
+
+    /// Register a hook run before a reloaded `T` replaces the current one
+    pub fn on_reload<T: AssetLoader>(&mut self, hook: impl FnMut(&T, T) -> Option<T> + 'static) {
+        self.loader_mut::<T>().reload_hook = Some(Box::new(hook));
+    }
+
+    fn check_watches(&mut self) {
+        if let Some(last) = self.last_watch
+            && last.elapsed() < self.watch_interval
+        {
+            return;
+        }
+        self.last_watch = Some(Instant::now());
+
+        let mut watches = std::mem::take(&mut self.watches);
+        watches.retain(|watch| watch.alive.strong_count() > 0);
+
+        for watch in &mut watches {
+            let Some(modified) = self.context.vfs.modified(&watch.path) else {
+                continue;
+            };
+            match watch.modified.replace(modified) {
+                Some(previous) if previous != modified => (watch.reload)(self),
+                _ => {}
+            }
+        }
+
+        watches.append(&mut self.watches);
+        self.watches = watches;
     }
 
     pub fn process_assets(&mut self) {
         let jobs: Vec<Job> = self.jobs_rx.lock().unwrap().try_iter().collect();
         for job in jobs {
             match job {
-                Job::Release(type_id, id) => self.remove(type_id, id),
+                Job::Release(type_id, id) => self.release(type_id, id),
             }
         }
 
+        if self.hot_reload {
+            self.check_watches();
+        }
+
+        self.poll_pending();
+        self.update_groups();
+
+        if let Some(budget) = self.budget {
+            self.evict_unused(budget);
+        }
+
+        for store in self.stores.values_mut() {
+            store.publish_events();
+        }
+    }
+
+    fn poll_pending(&mut self) {
         if self.pending.is_empty() {
             return;
         }
 
-        let waker = noop_waker();
-        let mut cx = Context::from_waker(&waker);
-
         let pending_len = self.pending.len();
         let pending = std::mem::replace(&mut self.pending, Vec::with_capacity(pending_len));
 
         for mut load in pending {
-            if load.poll.as_mut()(self, &mut cx).is_pending() {
-                self.pending.push(load);
+            // Only loads whose worker finished since the last call need polling
+            if load.flag.take() {
+                let waker = Waker::from(Arc::clone(&load.flag));
+                let mut cx = Context::from_waker(&waker);
+                if load.poll.as_mut()(self, &mut cx).is_ready() {
+                    continue;
+                }
             }
+            self.pending.push(load);
         }
     }
 
@@ -264,4 +1061,661 @@ impl AssetManager {
     pub fn is_ready<T: Asset>(&self, handle: &Handle<T>) -> bool {
         self.get(handle).is_some()
     }
+
+    pub fn load_state<T: Asset>(&self, handle: &Handle<T>) -> LoadState {
+        self.erased_state(TypeId::of::<T>(), handle.id())
+    }
+
+    fn erased_state(&self, type_id: TypeId, id: AssetId) -> LoadState {
+        self.stores
+            .get(&type_id)
+            .map_or(LoadState::Pending, |store| store.load_state(id))
+    }
+
+    /// Keep the asset alive after every handle to it is dropped, until [AssetManager::unpin]
+    pub fn pin<T: Asset>(&mut self, handle: &Handle<T>) {
+        self.pinned.insert(handle.id(), Box::new(handle.clone()));
+    }
+
+    /// Release a pin, the asset is unloaded if no other handle is left. Returns whether it was pinned
+    pub fn unpin(&mut self, id: AssetId) -> bool {
+        self.pinned.remove(&id).is_some()
+    }
+
+    pub fn is_pinned(&self, id: AssetId) -> bool {
+        self.pinned.contains_key(&id)
+    }
+
+    /// Every asset of type `T` with a live handle, sorted by id
+    pub fn live_assets<T: Asset>(&self) -> Vec<LiveAsset> {
+        let Some(store) = self.store::<T>() else {
+            return Vec::new();
+        };
+        let mut live: Vec<LiveAsset> = store
+            .refs
+            .iter()
+            .filter(|(_, rc)| rc.strong_count() > 0)
+            .map(|(&id, rc)| LiveAsset {
+                id,
+                ref_count: rc.strong_count(),
+                pinned: self.is_pinned(id),
+                state: store.load_state(id),
+            })
+            .collect();
+        live.sort_by_key(|asset| asset.id);
+        live
+    }
+
+    /// Create an empty [AssetGroup], its progress is updated by [AssetManager::process_assets]
+    pub fn group(&mut self) -> AssetGroup {
+        let shared = Rc::new(GroupShared::default());
+        self.groups.push(Rc::downgrade(&shared));
+        AssetGroup { shared }
+    }
+
+    /// Process assets until every member of `group` finished loading, successfully or not
+    ///
+    /// **NOTE**: This blocks the calling thread, use [AssetGroup::progress] to keep drawing a
+    /// loading screen instead.
+    pub fn wait_for(&mut self, group: &AssetGroup) -> GroupProgress {
+        loop {
+            self.process_assets();
+            let progress = group.progress();
+            if progress.is_done() {
+                return progress;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn update_groups(&mut self) {
+        let mut groups = std::mem::take(&mut self.groups);
+        groups.retain(|group| {
+            let Some(group) = group.upgrade() else {
+                return false;
+            };
+            let mut members = group.members.borrow_mut();
+            let mut progress = GroupProgress {
+                total: members.len(),
+                ..GroupProgress::default()
+            };
+            for member in members.iter_mut() {
+                if member.state.is_pending() {
+                    member.state = self.erased_state(member.type_id, member.id);
+                }
+                match member.state {
+                    LoadState::Pending => {}
+                    LoadState::Loaded => progress.loaded += 1,
+                    LoadState::Failed(_) => progress.failed += 1,
+                }
+            }
+            group.progress.set(progress);
+            if progress.is_done()
+                && let Some(waker) = group.waker.take()
+            {
+                waker.wake();
+            }
+            true
+        });
+        groups.append(&mut self.groups);
+        self.groups = groups;
+    }
+
+    /// Take the events of `T` published by the last [AssetManager::process_assets]
+    ///
+    /// Events not drained before the next [AssetManager::process_assets] are dropped.
+    pub fn drain_events<T: Asset>(&mut self) -> Vec<AssetEvent<T>> {
+        std::mem::take(&mut self.store_mut::<T>().events)
+    }
+
+    /// Take every load and reload error of `T` reported since the last call
+    pub fn drain_errors<T: AssetLoader>(&mut self) -> Vec<LoadError<T>> {
+        std::mem::take(&mut self.loader_mut::<T>().errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::vfs::{DiskSource, MemorySource, embed_dir};
+
+    #[derive(Debug)]
+    struct Text(String);
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("could not read {0}")]
+    struct TextError(String);
+
+    impl Asset for Text {
+        fn size_estimate(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    impl AssetLoader for Text {
+        type Key = PathBuf;
+        type Error = TextError;
+
+        type Decoded = String;
+
+        async fn decode(path: Self::Key, ctx: LoadContext) -> Result<String, Self::Error> {
+            let bytes = ctx
+                .read(&path)
+                .await
+                .map_err(|_| TextError(path.display().to_string()))?;
+            String::from_utf8(bytes).map_err(|_| TextError(path.display().to_string()))
+        }
+
+        fn finish(decoded: String) -> Result<Self, Self::Error> {
+            Ok(Text(decoded))
+        }
+
+        fn path(path: &Self::Key) -> Option<&Path> {
+            Some(path)
+        }
+    }
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rustyray-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn touch(path: &Path, contents: &str, offset: u64) {
+        std::fs::write(path, contents).unwrap();
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(offset))
+            .unwrap();
+    }
+
+    fn settle(manager: &mut AssetManager) {
+        for _ in 0..1000 {
+            manager.process_assets();
+            if manager.pending.is_empty() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        panic!("assets did not finish loading");
+    }
+
+    fn text<'a>(manager: &'a AssetManager, handle: &Handle<Text>) -> Option<&'a str> {
+        manager.get(handle).map(|text| text.0.as_str())
+    }
+
+    #[test]
+    fn hot_reload_replaces_asset_in_place() {
+        let path = temp_file("reload.txt", "first");
+        let mut manager = AssetManager::new();
+        manager.set_hot_reload(true);
+        manager.set_watch_interval(Duration::ZERO);
+
+        let handle = manager.load::<Text>(path.clone());
+        let other = handle.clone();
+        settle(&mut manager);
+        assert_eq!(text(&manager, &handle), Some("first"));
+
+        touch(&path, "second", 10);
+        settle(&mut manager);
+        assert_eq!(text(&manager, &other), Some("second"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn hot_reload_sees_edits_before_the_first_check() {
+        let path = temp_file("early-edit.txt", "first");
+        let mut manager = AssetManager::new();
+        manager.set_watch_interval(Duration::ZERO);
+
+        let handle = manager.load::<Text>(path.clone());
+        settle(&mut manager);
+        touch(&path, "second", 10);
+
+        manager.set_hot_reload(true);
+        settle(&mut manager);
+        assert_eq!(text(&manager, &handle), Some("second"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn hot_reload_is_opt_in() {
+        let path = temp_file("opt-in.txt", "first");
+        let mut manager = AssetManager::new();
+        manager.set_watch_interval(Duration::ZERO);
+
+        let handle = manager.load::<Text>(path.clone());
+        settle(&mut manager);
+
+        touch(&path, "second", 10);
+        settle(&mut manager);
+        assert_eq!(text(&manager, &handle), Some("first"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reload_hook_can_veto_and_post_process() {
+        let path = temp_file("hook.txt", "first");
+        let mut manager = AssetManager::new();
+        manager.set_hot_reload(true);
+        manager.set_watch_interval(Duration::ZERO);
+        manager.on_reload::<Text>(|_, new| (new.0 != "vetoed").then(|| Text(new.0.to_uppercase())));
+
+        let handle = manager.load::<Text>(path.clone());
+        settle(&mut manager);
+
+        touch(&path, "vetoed", 10);
+        settle(&mut manager);
+        assert_eq!(text(&manager, &handle), Some("first"));
+
+        touch(&path, "second", 20);
+        settle(&mut manager);
+        assert_eq!(text(&manager, &handle), Some("SECOND"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn load_state_reports_pending_loaded_and_failed() {
+        let path = temp_file("state.txt", "contents");
+        let missing = std::env::temp_dir().join("rustyray-missing-asset.txt");
+        let mut manager = AssetManager::new();
+
+        let loaded = manager.load::<Text>(path.clone());
+        let failed = manager.load::<Text>(missing.clone());
+        assert!(manager.load_state(&loaded).is_pending());
+        assert!(manager.load_state(&failed).is_pending());
+
+        settle(&mut manager);
+        assert!(manager.load_state(&loaded).is_loaded());
+        let LoadState::Failed(error) = manager.load_state(&failed) else {
+            panic!("expected the missing file to fail");
+        };
+        assert_eq!(
+            error.to_string(),
+            format!("could not read {}", missing.display())
+        );
+
+        let errors = manager.drain_errors::<Text>();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].id, failed.id());
+        assert!(manager.drain_errors::<Text>().is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn load_deduplicates_by_key() {
+        let path = temp_file("dedup.txt", "contents");
+        let mut manager = AssetManager::new();
+
+        let first = manager.load::<Text>(path.clone());
+        let second = manager.load::<Text>(path.clone());
+        assert_eq!(first.id(), second.id());
+        assert_eq!(manager.pending.len(), 1);
+
+        settle(&mut manager);
+        let third = manager.load::<Text>(path.clone());
+        assert_eq!(first.id(), third.id());
+
+        let id = first.id();
+        drop((first, second, third));
+        manager.process_assets();
+        let reloaded = manager.load::<Text>(path.clone());
+        assert_ne!(reloaded.id(), id);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn release_keeps_newer_load_of_same_key() {
+        let path = temp_file("release.txt", "contents");
+        let mut manager = AssetManager::new();
+
+        let first = manager.load::<Text>(path.clone());
+        drop(first);
+        let second = manager.load::<Text>(path.clone());
+        manager.process_assets();
+
+        let third = manager.load::<Text>(path.clone());
+        assert_eq!(second.id(), third.id());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn mounted_archive_replaces_filesystem() {
+        let pak_path = temp_file("assets.pak", "");
+        crate::core::pak::PakBuilder::new()
+            .add_bytes("text/packed.txt", b"packed".to_vec())
+            .unwrap()
+            .write(&pak_path)
+            .unwrap();
+        let on_disk = temp_file("on-disk.txt", "on disk");
+
+        let mut manager = AssetManager::new();
+        manager.mount_archive(Pak::open(&pak_path).unwrap());
+        let packed = manager.load::<Text>(PathBuf::from("text/packed.txt"));
+        let missing = manager.load::<Text>(on_disk.clone());
+        settle(&mut manager);
+        assert_eq!(text(&manager, &packed), Some("packed"));
+        assert!(manager.load_state(&missing).is_failed());
+
+        manager.unmount_all();
+        manager.mount(DiskSource::default(), 0);
+        let unpacked = manager.load::<Text>(on_disk.clone());
+        settle(&mut manager);
+        assert_eq!(text(&manager, &unpacked), Some("on disk"));
+
+        std::fs::remove_file(pak_path).unwrap();
+        std::fs::remove_file(on_disk).unwrap();
+    }
+
+    #[test]
+    fn embedded_files_load_from_memory() {
+        let mut manager = AssetManager::new();
+        manager.embed("hello.txt", b"hello");
+        manager.embed_all(embed_dir!("../assets"));
+
+        let hello = manager.load::<Text>(PathBuf::from("embedded://hello.txt"));
+        let missing = manager.load::<Text>(PathBuf::from("embedded://missing.txt"));
+        settle(&mut manager);
+        assert_eq!(text(&manager, &hello), Some("hello"));
+        assert!(manager.load_state(&missing).is_failed());
+
+        let wabbit =
+            futures_lite::future::block_on(manager.context.read("embedded://wabbit_alpha.png"))
+                .unwrap();
+        assert_eq!(wabbit, include_bytes!("../../../assets/wabbit_alpha.png"));
+        assert!(
+            futures_lite::future::block_on(manager.vfs().read("embedded://audio/sound.wav"))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn loaders_read_through_mounted_sources() {
+        let mut manager = AssetManager::new();
+        manager.unmount_all();
+        manager.mount(
+            MemorySource::new().with_file("base.txt", b"base".as_slice()),
+            0,
+        );
+        manager.mount(
+            MemorySource::new().with_file("base.txt", b"modded".as_slice()),
+            1,
+        );
+
+        let handle = manager.load::<Text>(PathBuf::from("base.txt"));
+        settle(&mut manager);
+        assert_eq!(text(&manager, &handle), Some("modded"));
+    }
+
+    #[derive(Debug)]
+    struct Threads {
+        decoded_on: std::thread::ThreadId,
+        finished_on: std::thread::ThreadId,
+    }
+
+    impl Asset for Threads {}
+
+    impl AssetLoader for Threads {
+        type Key = u32;
+        type Error = TextError;
+        type Decoded = std::thread::ThreadId;
+
+        async fn decode(_key: u32, _ctx: LoadContext) -> Result<Self::Decoded, Self::Error> {
+            Ok(std::thread::current().id())
+        }
+
+        fn finish(decoded_on: Self::Decoded) -> Result<Self, Self::Error> {
+            Ok(Threads {
+                decoded_on,
+                finished_on: std::thread::current().id(),
+            })
+        }
+    }
+
+    #[test]
+    fn decode_runs_on_worker_threads() {
+        let mut manager = AssetManager::new();
+        let handle = manager.load::<Threads>(0u32);
+        settle(&mut manager);
+
+        let threads = manager.get(&handle).unwrap();
+        assert_ne!(threads.decoded_on, std::thread::current().id());
+        assert_eq!(threads.finished_on, std::thread::current().id());
+    }
+
+    #[derive(Debug)]
+    struct Panics;
+
+    impl Asset for Panics {}
+
+    impl AssetLoader for Panics {
+        type Key = u32;
+        type Error = TextError;
+        type Decoded = ();
+
+        async fn decode(key: u32, _ctx: LoadContext) -> Result<(), Self::Error> {
+            panic!("decoder {key} blew up");
+        }
+
+        fn finish(_decoded: ()) -> Result<Self, Self::Error> {
+            Ok(Panics)
+        }
+    }
+
+    #[test]
+    fn panicking_decode_fails_the_load() {
+        let mut manager = AssetManager::new();
+        let panicked = manager.load::<Panics>(7u32);
+        settle(&mut manager);
+
+        let LoadState::Failed(error) = manager.load_state(&panicked) else {
+            panic!("expected the panicking load to fail");
+        };
+        assert_eq!(
+            error.to_string(),
+            "asset loader panicked: decoder 7 blew up"
+        );
+
+        // The worker survives and keeps loading
+        let handle = manager.load::<Threads>(1u32);
+        settle(&mut manager);
+        assert!(manager.is_ready(&handle));
+    }
+
+    #[test]
+    fn group_reports_progress_and_failures() {
+        let path = temp_file("group.txt", "contents");
+        let missing = std::env::temp_dir().join("rustyray-missing-group.txt");
+        let mut manager = AssetManager::new();
+
+        let loaded = manager.load::<Text>(path.clone());
+        let group = manager
+            .group()
+            .with(&loaded)
+            .with(&manager.load::<Text>(missing))
+            .with(&manager.load::<Threads>(1u32))
+            .with(&loaded);
+        assert_eq!(group.len(), 3);
+        assert!(!group.is_done());
+
+        let progress = manager.wait_for(&group);
+        assert_eq!(
+            progress,
+            GroupProgress {
+                total: 3,
+                loaded: 2,
+                failed: 1,
+            }
+        );
+        assert_eq!(progress.fraction(), 1.0);
+        assert_eq!(group.failures().len(), 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn group_keeps_members_alive_and_can_be_awaited() {
+        let mut manager = AssetManager::new();
+        let mut group = manager.group();
+        group.add(&manager.load::<Threads>(2u32));
+
+        let mut finished = Box::pin(group.finished());
+        let waker = Waker::from(Flag::new());
+        let mut cx = Context::from_waker(&waker);
+        assert!(finished.as_mut().poll(&mut cx).is_pending());
+
+        settle(&mut manager);
+        let Poll::Ready(progress) = finished.as_mut().poll(&mut cx) else {
+            panic!("expected the group to be finished");
+        };
+        assert_eq!(progress.loaded, 1);
+        assert_eq!(manager.store::<Threads>().unwrap().assets.len(), 1);
+    }
+
+    #[test]
+    fn weak_handles_upgrade_while_alive() {
+        let mut manager = AssetManager::new();
+        let handle = manager.insert(Text(String::from("weak")));
+        let weak = handle.downgrade();
+        assert_eq!(weak.upgrade().unwrap().id(), handle.id());
+
+        drop(handle);
+        manager.process_assets();
+        assert!(!weak.is_alive());
+        assert!(weak.upgrade().is_none());
+        assert!(manager.live_assets::<Text>().is_empty());
+    }
+
+    #[test]
+    fn pinned_assets_survive_their_handles() {
+        let mut manager = AssetManager::new();
+        let handle = manager.insert(Text(String::from("pinned")));
+        let other = manager.insert(Text(String::from("other")));
+        let id = handle.id();
+        manager.pin(&handle);
+        let weak = handle.downgrade();
+        drop(handle);
+        manager.process_assets();
+
+        let live = manager.live_assets::<Text>();
+        assert_eq!(live.len(), 2);
+        assert_eq!(live[0].id, id);
+        assert_eq!(live[0].ref_count, 1);
+        assert!(live[0].pinned && live[0].state.is_loaded());
+        assert!(!live[1].pinned);
+        assert_eq!(text(&manager, &weak.upgrade().unwrap()), Some("pinned"));
+
+        assert!(manager.unpin(id));
+        assert!(!manager.unpin(id));
+        manager.process_assets();
+        assert!(weak.upgrade().is_none());
+        assert_eq!(manager.live_assets::<Text>()[0].id, other.id());
+    }
+
+    /// Like [settle] but keeps the events of every processed frame
+    fn settle_events(manager: &mut AssetManager) -> Vec<&'static str> {
+        let mut kinds = Vec::new();
+        for _ in 0..1000 {
+            manager.process_assets();
+            kinds.extend(
+                manager
+                    .drain_events::<Text>()
+                    .iter()
+                    .map(|event| match event {
+                        AssetEvent::Added(_) => "added",
+                        AssetEvent::Modified(_) => "modified",
+                        AssetEvent::Removed(_) => "removed",
+                        AssetEvent::Failed(..) => "failed",
+                    }),
+            );
+            if manager.pending.is_empty() {
+                kinds.sort_unstable();
+                return kinds;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        panic!("assets did not finish loading");
+    }
+
+    #[test]
+    fn events_cover_insert_load_reload_and_release() {
+        let path = temp_file("events.txt", "first");
+        let missing = std::env::temp_dir().join("rustyray-missing-events.txt");
+        let mut manager = AssetManager::new();
+        manager.set_hot_reload(true);
+        manager.set_watch_interval(Duration::ZERO);
+
+        let inserted = manager.insert(Text(String::from("inserted")));
+        assert!(manager.drain_events::<Text>().is_empty());
+        manager.process_assets();
+        let events = manager.drain_events::<Text>();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], AssetEvent::Added(_)));
+        assert_eq!(events[0].id(), inserted.id());
+        assert!(events[0].handle().upgrade().is_some());
+
+        let _loaded = manager.load::<Text>(path.clone());
+        let _failed = manager.load::<Text>(missing);
+        assert_eq!(settle_events(&mut manager), ["added", "failed"]);
+
+        touch(&path, "second", 10);
+        assert_eq!(settle_events(&mut manager), ["modified"]);
+
+        drop(inserted);
+        assert_eq!(settle_events(&mut manager), ["removed"]);
+        assert!(settle_events(&mut manager).is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn memory_budget_keeps_and_evicts_unused_assets() {
+        let mut manager = AssetManager::new();
+        manager.unmount_all();
+        manager.mount(
+            MemorySource::new()
+                .with_file("a.txt", b"aaaa".as_slice())
+                .with_file("b.txt", b"bbbb".as_slice())
+                .with_file("c.txt", b"cccc".as_slice()),
+            0,
+        );
+        manager.set_memory_budget(Some(8));
+
+        let a = manager.load::<Text>(PathBuf::from("a.txt"));
+        let b = manager.load::<Text>(PathBuf::from("b.txt"));
+        settle(&mut manager);
+        let (a_id, b_id) = (a.id(), b.id());
+        drop(a);
+        drop(b);
+        manager.process_assets();
+        assert_eq!(manager.memory_usage(), 8);
+
+        // Revived without loading again
+        let a = manager.load::<Text>(PathBuf::from("a.txt"));
+        assert_eq!(a.id(), a_id);
+        assert_eq!(text(&manager, &a), Some("aaaa"));
+
+        // Over budget, the unused `b` is evicted while `a` is referenced
+        let c = manager.load::<Text>(PathBuf::from("c.txt"));
+        settle(&mut manager);
+        assert_eq!(manager.memory_usage(), 8);
+        let b = manager.load::<Text>(PathBuf::from("b.txt"));
+        assert_ne!(b.id(), b_id);
+        assert!(manager.load_state(&b).is_pending());
+        settle(&mut manager);
+        assert_eq!(text(&manager, &b), Some("bbbb"));
+        assert_eq!(manager.memory_usage(), 12);
+
+        drop((a, b, c));
+        manager.process_assets();
+        assert_eq!(manager.memory_usage(), 8);
+        manager.set_memory_budget(None);
+        assert_eq!(manager.memory_usage(), 0);
+    }
 }
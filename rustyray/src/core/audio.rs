@@ -3,7 +3,7 @@ use std::path::Path;
 use thiserror::Error;
 
 use rustyray_sys::{
-    audio::Sound as RaySound,
+    audio::{Sound as RaySound, Wave},
     ffi::{
         self, is_audio_device_ready, is_music_valid, is_sound_valid, is_wave_valid,
         load_music_stream_from_memory, load_sound_alias, load_sound_from_wave,
@@ -11,7 +11,7 @@ use rustyray_sys::{
     },
 };
 
-use super::assets::{Asset, AssetLoader, AssetManager, Handle};
+use super::assets::{Asset, AssetLoader, AssetManager, Handle, LoadContext};
 
 fn file_type_from_path(path: &str) -> Option<String> {
     let ext = Path::new(path).extension()?.to_str()?;
@@ -49,13 +49,13 @@ pub struct Sound {
 pub struct Music {
     inner: rustyray_sys::audio::Music,
     paused: bool,
-    _buffer: Vec<u8>,
+    /// Encoded file, raylib decodes from it while streaming
+    buffer: Vec<u8>,
 }
 
 impl Sound {
-    pub(crate) fn from_wave(wave: rustyray_sys::audio::Wave) -> Result<Self, SoundLoadError> {
+    pub(crate) fn from_wave(wave: &Wave) -> Result<Self, SoundLoadError> {
         let inner = unsafe { load_sound_from_wave(wave.clone()) };
-        unsafe { unload_wave(wave) };
         if !unsafe { is_sound_valid(inner.clone()) } {
             return Err(SoundLoadError::DecodeFailed);
         }
@@ -195,21 +195,43 @@ impl Drop for Music {
     }
 }
 
-impl Asset for Sound {}
+impl Asset for Sound {
+    fn size_estimate(&self) -> usize {
+        // Aliases share the buffer of their source
+        if self.is_alias {
+            return 0;
+        }
+        let stream = &self.inner.stream;
+        self.inner.frame_count as usize
+            * stream.channels as usize
+            * (stream.sample_size as usize).div_ceil(8)
+    }
+}
+
+/// Samples decoded on a worker thread, waiting to be uploaded by [AssetLoader::finish]
+#[derive(Debug)]
+pub struct DecodedWave(Wave);
+
+// SAFETY: the sample buffer is owned by this value and only touched through it
+unsafe impl Send for DecodedWave {}
+
+impl Drop for DecodedWave {
+    fn drop(&mut self) {
+        unsafe { unload_wave(self.0.clone()) };
+    }
+}
 
 impl AssetLoader for Sound {
     type Key = String;
     type Error = SoundLoadError;
+    type Decoded = DecodedWave;
 
-    async fn load(path: Self::Key) -> Result<Self, Self::Error> {
-        if !unsafe { is_audio_device_ready() } {
-            return Err(SoundLoadError::AudioDeviceNotReady);
-        }
-
+    async fn decode(path: Self::Key, ctx: LoadContext) -> Result<DecodedWave, Self::Error> {
         let file_type =
             file_type_from_path(&path).ok_or(SoundLoadError::FileNotFound(path.clone()))?;
 
-        let bytes = async_fs::read(&path)
+        let bytes = ctx
+            .read(&path)
             .await
             .map_err(|_| SoundLoadError::FileNotFound(path.clone()))?;
 
@@ -222,29 +244,53 @@ impl AssetLoader for Sound {
             return Err(SoundLoadError::DecodeFailed);
         }
 
-        Self::from_wave(wave)
+        Ok(DecodedWave(wave))
+    }
+
+    fn finish(decoded: DecodedWave) -> Result<Self, Self::Error> {
+        if !unsafe { is_audio_device_ready() } {
+            return Err(SoundLoadError::AudioDeviceNotReady);
+        }
+
+        Self::from_wave(&decoded.0)
+    }
+
+    fn path(path: &Self::Key) -> Option<&Path> {
+        Some(Path::new(path))
     }
 }
 
-impl Asset for Music {}
+impl Asset for Music {
+    fn size_estimate(&self) -> usize {
+        // Streamed while playing, only the encoded file stays in memory
+        self.buffer.len()
+    }
+}
 
 impl AssetLoader for Music {
     type Key = String;
     type Error = MusicLoadError;
+    /// File type and the encoded file, music is streamed so it is decoded while playing
+    type Decoded = (CString, Vec<u8>);
 
-    async fn load(path: Self::Key) -> Result<Self, Self::Error> {
-        if !unsafe { is_audio_device_ready() } {
-            return Err(MusicLoadError::AudioDeviceNotReady);
-        }
-
+    async fn decode(path: Self::Key, ctx: LoadContext) -> Result<Self::Decoded, Self::Error> {
         let file_type =
             file_type_from_path(&path).ok_or(MusicLoadError::FileNotFound(path.clone()))?;
 
-        let bytes = async_fs::read(&path)
+        let bytes = ctx
+            .read(&path)
             .await
             .map_err(|_| MusicLoadError::FileNotFound(path.clone()))?;
 
         let file_type = CString::new(file_type).map_err(|_| MusicLoadError::DecodeFailed)?;
+        Ok((file_type, bytes))
+    }
+
+    fn finish((file_type, bytes): Self::Decoded) -> Result<Self, Self::Error> {
+        if !unsafe { is_audio_device_ready() } {
+            return Err(MusicLoadError::AudioDeviceNotReady);
+        }
+
         let inner = unsafe {
             load_music_stream_from_memory(file_type.as_ptr(), bytes.as_ptr(), bytes.len() as i32)
         };
@@ -256,7 +302,11 @@ impl AssetLoader for Music {
         Ok(Self {
             inner,
             paused: false,
-            _buffer: bytes,
+            buffer: bytes,
         })
     }
+
+    fn path(path: &Self::Key) -> Option<&Path> {
+        Some(Path::new(path))
+    }
 }
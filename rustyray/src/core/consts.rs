@@ -10,3 +10,6 @@ pub type MouseButton = rustyray_sys::consts::MouseButton;
 pub type MouseCursor = rustyray_sys::consts::MouseCursor;
 pub type Gesture = rustyray_sys::consts::Gesture;
 pub type ConfigFlag = rustyray_sys::consts::ConfigFlag;
+pub type PixelFormat = rustyray_sys::consts::PixelFormat;
+pub type TextureFilter = rustyray_sys::consts::TextureFilter;
+pub type TextureWrap = rustyray_sys::consts::TextureWrap;
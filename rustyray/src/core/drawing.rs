@@ -1,8 +1,9 @@
 use super::{
     assets::{AssetManager, Handle},
     color::Color,
-    image::{RenderTexture, Texture},
-    math::{Rectangle, Vector2, Vector2i},
+    image::{RenderTexture, SubTexture, Texture},
+    math::{Rectangle, Spline, SplineKind, Vector2, Vector2i},
+    text::{Font, SdfEffects, TextLayout, with_font_shader},
 };
 use rustyray_sys::ffi;
 use std::{ffi::CString, fmt::Debug};
@@ -114,6 +115,20 @@ pub trait Draw {
         }
     }
 
+    /// Draw a region of an atlas at `position`, unscaled
+    #[inline]
+    fn draw_sub_texture(&self, sub_texture: &SubTexture, position: Vector2, tint: Color) {
+        let region = sub_texture.region;
+        self.draw_texture_pro(
+            &sub_texture.texture,
+            region,
+            Rectangle::new(position.x, position.y, region.width, region.height),
+            Vector2::ZERO,
+            0.0,
+            tint,
+        );
+    }
+
     /// Draw a line
     #[inline]
     fn draw_line(&self, start: Vector2, end: Vector2, thickness: f32, color: Color) {
@@ -187,6 +202,340 @@ pub trait Draw {
         }
     }
 
+    /// Draw a convex polygon, fanned from `points[0]`, in counter-clockwise order
+    #[inline]
+    fn draw_triangle_fan(&self, points: &[Vector2], color: Color) {
+        unsafe {
+            ffi::draw_triangle_fan(points.as_ptr().cast(), point_count(points), color);
+        }
+    }
+
+    /// Draw a strip where every point forms a triangle with the two before it
+    #[inline]
+    fn draw_triangle_strip(&self, points: &[Vector2], color: Color) {
+        unsafe {
+            ffi::draw_triangle_strip(points.as_ptr().cast(), point_count(points), color);
+        }
+    }
+
+    /// Draw connected 1px lines through `points`
+    #[inline]
+    fn draw_line_strip(&self, points: &[Vector2], color: Color) {
+        unsafe {
+            ffi::draw_line_strip(points.as_ptr().cast(), point_count(points), color);
+        }
+    }
+
+    /// Draw a line with cubic-bezier ease in-out
+    #[inline]
+    fn draw_line_bezier(&self, start: Vector2, end: Vector2, thickness: f32, color: Color) {
+        unsafe {
+            ffi::draw_line_bezier(start.into(), end.into(), thickness, color);
+        }
+    }
+
+    /// Draw a thick [Spline], picking the raylib routine for its kind
+    #[inline]
+    fn draw_spline(&self, spline: &Spline, thickness: f32, color: Color) {
+        match spline.kind {
+            SplineKind::Linear => self.draw_spline_linear(&spline.points, thickness, color),
+            SplineKind::CatmullRom => {
+                self.draw_spline_catmull_rom(&spline.points, thickness, color)
+            }
+            SplineKind::Basis => self.draw_spline_basis(&spline.points, thickness, color),
+            SplineKind::QuadraticBezier => {
+                self.draw_spline_bezier_quadratic(&spline.points, thickness, color)
+            }
+            SplineKind::CubicBezier => {
+                self.draw_spline_bezier_cubic(&spline.points, thickness, color)
+            }
+        }
+    }
+
+    /// Draw thick lines through `points`, at least 2
+    #[inline]
+    fn draw_spline_linear(&self, points: &[Vector2], thickness: f32, color: Color) {
+        unsafe {
+            ffi::draw_spline_linear(
+                points.as_ptr().cast(),
+                point_count(points),
+                thickness,
+                color,
+            );
+        }
+    }
+
+    /// Draw a thick B-spline, at least 4 points
+    #[inline]
+    fn draw_spline_basis(&self, points: &[Vector2], thickness: f32, color: Color) {
+        unsafe {
+            ffi::draw_spline_basis(
+                points.as_ptr().cast(),
+                point_count(points),
+                thickness,
+                color,
+            );
+        }
+    }
+
+    /// Draw a thick Catmull-Rom spline, at least 4 points
+    #[inline]
+    fn draw_spline_catmull_rom(&self, points: &[Vector2], thickness: f32, color: Color) {
+        unsafe {
+            ffi::draw_spline_catmull_rom(
+                points.as_ptr().cast(),
+                point_count(points),
+                thickness,
+                color,
+            );
+        }
+    }
+
+    /// Draw thick quadratic beziers, `points` as `[start, control, end, control, end, ...]`
+    #[inline]
+    fn draw_spline_bezier_quadratic(&self, points: &[Vector2], thickness: f32, color: Color) {
+        unsafe {
+            ffi::draw_spline_bezier_quadratic(
+                points.as_ptr().cast(),
+                point_count(points),
+                thickness,
+                color,
+            );
+        }
+    }
+
+    /// Draw thick cubic beziers, `points` as `[start, control, control, end, control, control, end, ...]`
+    #[inline]
+    fn draw_spline_bezier_cubic(&self, points: &[Vector2], thickness: f32, color: Color) {
+        unsafe {
+            ffi::draw_spline_bezier_cubic(
+                points.as_ptr().cast(),
+                point_count(points),
+                thickness,
+                color,
+            );
+        }
+    }
+
+    /// Draw a filled convex polygon, `points` in counter-clockwise order
+    #[inline]
+    fn draw_polygon(&self, points: &[Vector2], color: Color) {
+        self.draw_triangle_fan(points, color);
+    }
+
+    /// Draw the closed outline of `points`
+    fn draw_polygon_lines(&self, points: &[Vector2], thickness: f32, color: Color) {
+        if points.len() < 2 {
+            return;
+        }
+        for (start, end) in points.iter().zip(points.iter().cycle().skip(1)) {
+            self.draw_line(*start, *end, thickness, color);
+        }
+    }
+
+    /// Draw a regular polygon, `rotation` in degrees
+    #[inline]
+    fn draw_poly(&self, center: Vector2, sides: i32, radius: f32, rotation: f32, color: Color) {
+        unsafe {
+            ffi::draw_poly(center.into(), sides, radius, rotation, color);
+        }
+    }
+
+    #[inline]
+    fn draw_poly_lines(
+        &self,
+        center: Vector2,
+        sides: i32,
+        radius: f32,
+        rotation: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        unsafe {
+            ffi::draw_poly_lines_ex(center.into(), sides, radius, rotation, thickness, color);
+        }
+    }
+
+    /// Draw a rectangle with corners rounded by `roundness` (0.0 to 1.0 of the shorter side)
+    #[inline]
+    fn draw_rect_rounded(&self, rect: Rectangle, roundness: f32, segments: i32, color: Color) {
+        unsafe {
+            ffi::draw_rectangle_rounded(rect.into(), roundness, segments, color);
+        }
+    }
+
+    #[inline]
+    fn draw_rect_rounded_lines(
+        &self,
+        rect: Rectangle,
+        roundness: f32,
+        segments: i32,
+        thickness: f32,
+        color: Color,
+    ) {
+        unsafe {
+            ffi::draw_rectangle_rounded_lines_ex(
+                rect.into(),
+                roundness,
+                segments,
+                thickness,
+                color,
+            );
+        }
+    }
+
+    /// Draw a rectangle fading from `top` to `bottom`
+    #[inline]
+    fn draw_rect_gradient_v(&self, rect: Rectangle, top: Color, bottom: Color) {
+        unsafe {
+            ffi::draw_rectangle_gradient_v(
+                rect.x as i32,
+                rect.y as i32,
+                rect.width as i32,
+                rect.height as i32,
+                top,
+                bottom,
+            );
+        }
+    }
+
+    /// Draw a rectangle fading from `left` to `right`
+    #[inline]
+    fn draw_rect_gradient_h(&self, rect: Rectangle, left: Color, right: Color) {
+        unsafe {
+            ffi::draw_rectangle_gradient_h(
+                rect.x as i32,
+                rect.y as i32,
+                rect.width as i32,
+                rect.height as i32,
+                left,
+                right,
+            );
+        }
+    }
+
+    /// Draw a rectangle interpolating a color per corner
+    #[inline]
+    fn draw_rect_gradient(
+        &self,
+        rect: Rectangle,
+        top_left: Color,
+        bottom_left: Color,
+        top_right: Color,
+        bottom_right: Color,
+    ) {
+        unsafe {
+            ffi::draw_rectangle_gradient_ex(
+                rect.into(),
+                top_left,
+                bottom_left,
+                top_right,
+                bottom_right,
+            );
+        }
+    }
+
+    /// Draw a pie slice, angles in degrees
+    #[inline]
+    fn draw_circle_sector(
+        &self,
+        center: Vector2,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        segments: i32,
+        color: Color,
+    ) {
+        unsafe {
+            ffi::draw_circle_sector(
+                center.into(),
+                radius,
+                start_angle,
+                end_angle,
+                segments,
+                color,
+            );
+        }
+    }
+
+    #[inline]
+    fn draw_circle_sector_lines(
+        &self,
+        center: Vector2,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        segments: i32,
+        color: Color,
+    ) {
+        unsafe {
+            ffi::draw_circle_sector_lines(
+                center.into(),
+                radius,
+                start_angle,
+                end_angle,
+                segments,
+                color,
+            );
+        }
+    }
+
+    /// Draw a ring, or an arc of one, between `inner_radius` and `outer_radius`
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn draw_ring(
+        &self,
+        center: Vector2,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        segments: i32,
+        color: Color,
+    ) {
+        unsafe {
+            ffi::draw_ring(
+                center.into(),
+                inner_radius,
+                outer_radius,
+                start_angle,
+                end_angle,
+                segments,
+                color,
+            );
+        }
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn draw_ring_lines(
+        &self,
+        center: Vector2,
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        segments: i32,
+        color: Color,
+    ) {
+        unsafe {
+            ffi::draw_ring_lines(
+                center.into(),
+                inner_radius,
+                outer_radius,
+                start_angle,
+                end_angle,
+                segments,
+                color,
+            );
+        }
+    }
+
+    #[inline]
+    fn draw_ellipse_lines(&self, center: Vector2i, radius: Vector2, color: Color) {
+        unsafe { ffi::draw_ellipse_lines(center.x, center.y, radius.x, radius.y, color) }
+    }
+
     #[inline]
     fn measure_text<T>(&self, text: T, size: i32) -> i32
     where
@@ -211,6 +560,184 @@ pub trait Draw {
             ffi::draw_text(cstr.as_ptr(), pos_x, pos_y, size, tint);
         }
     }
+
+    /// Size of `text` drawn with [Draw::draw_text_ex], zero while `font` is loading
+    #[inline]
+    fn measure_text_ex<T>(&self, font: &Handle<Font>, text: T, size: f32, spacing: f32) -> Vector2
+    where
+        T: AsRef<str>,
+    {
+        self.assets().get(font).map_or(Vector2::ZERO, |font| {
+            font.measure(text.as_ref(), size, spacing)
+        })
+    }
+
+    /// Draw text with a loaded [Font], `spacing` is added between characters
+    #[inline]
+    fn draw_text_ex<T>(
+        &self,
+        font: &Handle<Font>,
+        text: T,
+        position: Vector2,
+        size: f32,
+        spacing: f32,
+        tint: Color,
+    ) where
+        T: AsRef<str>,
+    {
+        self.draw_text_pro(
+            font,
+            text,
+            position,
+            Vector2::ZERO,
+            0.0,
+            size,
+            spacing,
+            tint,
+        );
+    }
+
+    /// Draw text rotated by `rotation` degrees around `origin`, relative to `position`
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn draw_text_pro<T>(
+        &self,
+        font: &Handle<Font>,
+        text: T,
+        position: Vector2,
+        origin: Vector2,
+        rotation: f32,
+        size: f32,
+        spacing: f32,
+        tint: Color,
+    ) where
+        T: AsRef<str>,
+    {
+        let Some(font) = self.assets().get(font) else {
+            return;
+        };
+        let Ok(cstr) = CString::new(text.as_ref()) else {
+            return;
+        };
+        with_font_shader(font, &SdfEffects::default(), || unsafe {
+            ffi::draw_text_pro(
+                font.as_ray(),
+                cstr.as_ptr(),
+                position.into(),
+                origin.into(),
+                rotation,
+                size,
+                spacing,
+                tint,
+            );
+        });
+    }
+
+    /// Draw text with an outline and glow, only SDF fonts draw the effects
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn draw_text_sdf<T>(
+        &self,
+        font: &Handle<Font>,
+        text: T,
+        position: Vector2,
+        size: f32,
+        spacing: f32,
+        tint: Color,
+        effects: &SdfEffects,
+    ) where
+        T: AsRef<str>,
+    {
+        let Some(font) = self.assets().get(font) else {
+            return;
+        };
+        let Ok(cstr) = CString::new(text.as_ref()) else {
+            return;
+        };
+        with_font_shader(font, effects, || unsafe {
+            ffi::draw_text_ex(
+                font.as_ray(),
+                cstr.as_ptr(),
+                position.into(),
+                size,
+                spacing,
+                tint,
+            );
+        });
+    }
+
+    /// Draw a [TextLayout] with the font it was laid out with, unstyled text uses `tint`
+    ///
+    /// Works with [Font::default_font] as well as loaded fonts, get those with
+    /// `d.assets().get(&handle)`.
+    fn draw_text_layout(&self, font: &Font, layout: &TextLayout, tint: Color) {
+        self.draw_text_layout_sdf(font, layout, tint, &SdfEffects::default());
+    }
+
+    /// [Draw::draw_text_layout] with an outline and glow, only SDF fonts draw the effects
+    fn draw_text_layout_sdf(
+        &self,
+        font: &Font,
+        layout: &TextLayout,
+        tint: Color,
+        effects: &SdfEffects,
+    ) {
+        let ray_font = font.as_ray();
+        let size = layout.font_size();
+        with_font_shader(font, effects, || {
+            for glyph in layout.glyphs() {
+                if glyph.codepoint.is_whitespace() {
+                    continue;
+                }
+                unsafe {
+                    ffi::draw_text_codepoint(
+                        ray_font.clone(),
+                        glyph.codepoint as i32,
+                        glyph.position.into(),
+                        size,
+                        glyph.style.color.unwrap_or(tint),
+                    );
+                }
+            }
+        });
+
+        let thickness = (size / 16.0).max(1.0);
+        for run in layout.runs() {
+            let color = run.style.color.unwrap_or(tint);
+            let bounds = run.bounds;
+            if run.style.underline {
+                let y = bounds.y + bounds.height - thickness;
+                self.draw_rect(Rectangle::new(bounds.x, y, bounds.width, thickness), color);
+            }
+            if run.style.strikethrough {
+                let y = bounds.y + (bounds.height - thickness) / 2.0;
+                self.draw_rect(Rectangle::new(bounds.x, y, bounds.width, thickness), color);
+            }
+        }
+    }
+
+    /// Draw a single character, `position` is the top left of its line
+    #[inline]
+    fn draw_text_codepoint(
+        &self,
+        font: &Handle<Font>,
+        codepoint: char,
+        position: Vector2,
+        size: f32,
+        tint: Color,
+    ) {
+        let Some(font) = self.assets().get(font) else {
+            return;
+        };
+        with_font_shader(font, &SdfEffects::default(), || unsafe {
+            ffi::draw_text_codepoint(font.as_ray(), codepoint as i32, position.into(), size, tint);
+        });
+    }
+}
+
+/// Length of a point slice as raylib's `int` count
+fn point_count(points: &[Vector2]) -> i32 {
+    points.len().try_into().unwrap_or(i32::MAX)
 }
 
 pub struct DrawHandler<'a> {
@@ -0,0 +1,655 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+use rustyray_sys::texture::TextureLoadError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{Image, ImageError, ImageExportError, Texture};
+use crate::core::assets::{AssetManager, Handle};
+use crate::core::color::Color;
+use crate::core::math::Rectangle;
+use crate::core::vfs::Vfs;
+
+#[derive(Debug, Error)]
+pub enum AtlasError {
+    #[error(transparent)]
+    Image(#[from] ImageError),
+    #[error(transparent)]
+    Export(#[from] ImageExportError),
+    #[error(transparent)]
+    Texture(#[from] TextureLoadError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("an image named {0:?} was already added")]
+    DuplicateName(String),
+    #[error("the images do not fit in a {0}x{0} atlas")]
+    TooLarge(i32),
+    #[error("unknown manifest extension: {0}")]
+    UnknownManifest(String),
+    #[error("invalid manifest: {0}")]
+    Manifest(String),
+}
+
+/// Packed width, height and the position of every cell
+type Packing = (i32, i32, Vec<(i32, i32)>);
+
+/// Packs many images into a single [Atlas] with a skyline bottom-left packer
+///
+/// ```no_run
+/// # use rustyray::prelude::*;
+/// # fn build(assets: &mut AssetManager) -> Result<(), AtlasError> {
+/// let atlas = AtlasBuilder::new()
+///     .padding(2)
+///     .extrude(1)
+///     .with_file("bunny", "assets/wabbit_alpha.png")?
+///     .with("marker", Image::new(4, 4, Color::RED))
+///     .build()?;
+/// atlas.export("build/sprites.png", "build/sprites.json")?;
+/// let atlas = atlas.upload(assets)?;
+/// let bunny = atlas.sub_texture("bunny").unwrap();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AtlasBuilder {
+    images: Vec<(String, Image)>,
+    padding: i32,
+    extrude: i32,
+    max_size: i32,
+    power_of_two: bool,
+}
+
+impl Default for AtlasBuilder {
+    fn default() -> Self {
+        Self {
+            images: Vec::new(),
+            padding: 0,
+            extrude: 0,
+            max_size: 4096,
+            power_of_two: false,
+        }
+    }
+}
+
+impl AtlasBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empty pixels between images and around the border
+    pub fn padding(mut self, padding: i32) -> Self {
+        self.padding = padding.max(0);
+        self
+    }
+
+    /// Repeat the edge pixels of every image outwards, hides bleeding with linear filtering
+    pub fn extrude(mut self, extrude: i32) -> Self {
+        self.extrude = extrude.max(0);
+        self
+    }
+
+    /// Largest width and height the atlas may grow to, 4096 by default
+    pub fn max_size(mut self, max_size: i32) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Round the atlas size up to powers of two
+    pub fn power_of_two(mut self, power_of_two: bool) -> Self {
+        self.power_of_two = power_of_two;
+        self
+    }
+
+    pub fn add(&mut self, name: impl Into<String>, image: Image) -> &mut Self {
+        self.images.push((name.into(), image));
+        self
+    }
+
+    pub fn with(mut self, name: impl Into<String>, image: Image) -> Self {
+        self.add(name, image);
+        self
+    }
+
+    /// Read straight from disk, use [AtlasBuilder::add_vfs_file] for packed or embedded files
+    pub fn add_file(
+        &mut self,
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<&mut Self, AtlasError> {
+        let image = Image::from_file(path)?;
+        Ok(self.add(name, image))
+    }
+
+    pub fn with_file(
+        mut self,
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, AtlasError> {
+        self.add_file(name, path)?;
+        Ok(self)
+    }
+
+    /// Read through `vfs`, e.g. [AssetManager::vfs], so mounted paks and `embedded://` files work
+    pub fn add_vfs_file(
+        &mut self,
+        name: impl Into<String>,
+        vfs: &Vfs,
+        path: impl AsRef<Path>,
+    ) -> Result<&mut Self, AtlasError> {
+        let path = path.as_ref();
+        let bytes = futures_lite::future::block_on(vfs.read(path))?;
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("png");
+        let image = Image::from_memory(&bytes, extension)?;
+        Ok(self.add(name, image))
+    }
+
+    pub fn with_vfs_file(
+        mut self,
+        name: impl Into<String>,
+        vfs: &Vfs,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, AtlasError> {
+        self.add_vfs_file(name, vfs, path)?;
+        Ok(self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    pub fn build(&self) -> Result<Atlas, AtlasError> {
+        let mut names = BTreeMap::new();
+        for (index, (name, _)) in self.images.iter().enumerate() {
+            if names.insert(name.as_str(), index).is_some() {
+                return Err(AtlasError::DuplicateName(name.clone()));
+            }
+        }
+
+        // Every image reserves its extruded size plus the padding on its right and bottom
+        let border = 2 * self.extrude + self.padding;
+        let cells: Vec<(i32, i32)> = self
+            .images
+            .iter()
+            .map(|(_, image)| (image.width() + border, image.height() + border))
+            .collect();
+        let (width, height, positions) = self.pack(&cells)?;
+
+        let mut pixels = vec![Color::new(0, 0, 0, 0); width as usize * height as usize];
+        let mut regions = BTreeMap::new();
+        for ((name, image), (x, y)) in self.images.iter().zip(positions) {
+            let (x, y) = (x + self.padding, y + self.padding);
+            self.blit(&mut pixels, width, image, x, y);
+            regions.insert(
+                name.clone(),
+                Rectangle::new(
+                    (x + self.extrude) as f32,
+                    (y + self.extrude) as f32,
+                    image.width() as f32,
+                    image.height() as f32,
+                ),
+            );
+        }
+
+        Ok(Atlas {
+            image: Image::from_colors(width, height, pixels)?,
+            regions,
+        })
+    }
+
+    /// Try every candidate width and keep the smallest atlas
+    fn pack(&self, cells: &[(i32, i32)]) -> Result<Packing, AtlasError> {
+        let limit = self.max_size - self.padding;
+        let widest = cells.iter().map(|&(w, _)| w).max().unwrap_or(0);
+        let area: i64 = cells.iter().map(|&(w, h)| w as i64 * h as i64).sum();
+        // The padding alone has to leave room for at least one pixel
+        if limit < 1 || widest > limit {
+            return Err(AtlasError::TooLarge(self.max_size));
+        }
+
+        // Tallest first packs tighter with a skyline
+        let mut order: Vec<usize> = (0..cells.len()).collect();
+        order.sort_by_key(|&i| (std::cmp::Reverse(cells[i].1), std::cmp::Reverse(cells[i].0)));
+
+        let mut candidates: Vec<i32> = std::iter::successors(Some(widest.max(1)), |w| {
+            (*w < limit).then(|| (w * 2).min(limit))
+        })
+        .collect();
+        candidates.push(((area as f64).sqrt().ceil() as i32).clamp(widest.max(1), limit));
+
+        let mut best: Option<(i64, Packing)> = None;
+        for inner_width in candidates {
+            let Some((used_width, used_height, positions)) =
+                skyline(inner_width, limit, cells, &order)
+            else {
+                continue;
+            };
+            let width = self.fit(used_width + self.padding);
+            let height = self.fit(used_height + self.padding);
+            if width > self.max_size || height > self.max_size {
+                continue;
+            }
+            let score = width as i64 * height as i64;
+            if best
+                .as_ref()
+                .is_none_or(|(best_score, (best_w, best_h, _))| {
+                    // Prefer squarer atlases on ties
+                    (score, (width - height).abs()) < (*best_score, (best_w - best_h).abs())
+                })
+            {
+                best = Some((score, (width, height, positions)));
+            }
+        }
+
+        best.map(|(_, packing)| packing)
+            .ok_or(AtlasError::TooLarge(self.max_size))
+    }
+
+    fn fit(&self, size: i32) -> i32 {
+        let size = size.max(1);
+        if self.power_of_two {
+            (size as u32).next_power_of_two() as i32
+        } else {
+            size
+        }
+    }
+
+    /// Copy `image` with its extruded edges so that its top left pixel lands at `x + extrude`
+    fn blit(&self, pixels: &mut [Color], atlas_width: i32, image: &Image, x: i32, y: i32) {
+        let (width, height) = (image.width(), image.height());
+        if width == 0 || height == 0 {
+            return;
+        }
+        let colors = image.to_colors();
+        let extrude = self.extrude;
+        for dy in -extrude..height + extrude {
+            let src_y = dy.clamp(0, height - 1);
+            for dx in -extrude..width + extrude {
+                let src_x = dx.clamp(0, width - 1);
+                let dst = (y + extrude + dy) as usize * atlas_width as usize
+                    + (x + extrude + dx) as usize;
+                pixels[dst] = colors[(src_y * width + src_x) as usize];
+            }
+        }
+    }
+}
+
+/// Place `cells` in `order` inside a strip `width` wide, returns the used size and positions
+fn skyline(width: i32, max_height: i32, cells: &[(i32, i32)], order: &[usize]) -> Option<Packing> {
+    // (x, y, width) segments of the skyline, left to right
+    let mut nodes = vec![(0, 0, width)];
+    let mut positions = vec![(0, 0); cells.len()];
+    let (mut used_width, mut used_height) = (0, 0);
+
+    for &index in order {
+        let (w, h) = cells[index];
+        if w == 0 || h == 0 {
+            continue;
+        }
+
+        let mut best: Option<(i32, i32, usize)> = None;
+        for start in 0..nodes.len() {
+            let x = nodes[start].0;
+            if x + w > width {
+                break;
+            }
+            // Resting height is the highest segment under the span
+            let mut y = 0;
+            let mut covered = 0;
+            for node in &nodes[start..] {
+                y = y.max(node.1);
+                covered = node.0 + node.2 - x;
+                if covered >= w {
+                    break;
+                }
+            }
+            if covered < w || y + h > max_height {
+                continue;
+            }
+            if best.is_none_or(|(best_y, best_x, _)| (y + h, x) < (best_y, best_x)) {
+                best = Some((y + h, x, start));
+            }
+        }
+
+        let (bottom, x, start) = best?;
+        positions[index] = (x, bottom - h);
+        used_width = used_width.max(x + w);
+        used_height = used_height.max(bottom);
+
+        // Replace the covered segments with the new top edge
+        let mut end = start;
+        while end < nodes.len() && nodes[end].0 + nodes[end].2 <= x + w {
+            end += 1;
+        }
+        if end < nodes.len() && nodes[end].0 < x + w {
+            let right = nodes[end].0 + nodes[end].2;
+            nodes[end] = (x + w, nodes[end].1, right - (x + w));
+        }
+        nodes.splice(start..end, [(x, bottom, w)]);
+        nodes.dedup_by(|next, prev| {
+            let merge = prev.1 == next.1;
+            if merge {
+                prev.2 += next.2;
+            }
+            merge
+        });
+    }
+
+    Some((used_width, used_height, positions))
+}
+
+/// Packed pixels and the named regions inside them
+#[derive(Debug, Clone)]
+pub struct Atlas {
+    image: Image,
+    regions: BTreeMap<String, Rectangle>,
+}
+
+impl Atlas {
+    /// Reassemble an atlas baked offline with [Atlas::export]
+    pub fn from_manifest(image: Image, manifest: &AtlasManifest) -> Self {
+        Self {
+            image,
+            regions: manifest
+                .regions
+                .iter()
+                .map(|(name, region)| (name.clone(), (*region).into()))
+                .collect(),
+        }
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    pub fn regions(&self) -> &BTreeMap<String, Rectangle> {
+        &self.regions
+    }
+
+    pub fn region(&self, name: &str) -> Option<Rectangle> {
+        self.regions.get(name).copied()
+    }
+
+    /// Describe the regions, `image` is the path of the atlas image as written in the manifest
+    pub fn manifest(&self, image: impl Into<String>) -> AtlasManifest {
+        AtlasManifest {
+            image: image.into(),
+            width: self.image.width(),
+            height: self.image.height(),
+            regions: self
+                .regions
+                .iter()
+                .map(|(name, region)| (name.clone(), (*region).into()))
+                .collect(),
+        }
+    }
+
+    /// Write the atlas image and a JSON or TOML manifest, picked from the manifest extension
+    pub fn export(
+        &self,
+        image_path: impl AsRef<Path>,
+        manifest_path: impl AsRef<Path>,
+    ) -> Result<(), AtlasError> {
+        let (image_path, manifest_path) = (image_path.as_ref(), manifest_path.as_ref());
+        let format = ManifestFormat::from_path(manifest_path)
+            .ok_or_else(|| AtlasError::UnknownManifest(manifest_path.display().to_string()))?;
+
+        // Reference the image relative to the manifest when they share a directory
+        let image_name = match (image_path.parent(), manifest_path.parent()) {
+            (Some(image_dir), Some(manifest_dir)) if image_dir == manifest_dir => image_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            _ => None,
+        }
+        .unwrap_or_else(|| image_path.display().to_string());
+
+        self.image.export(image_path)?;
+        std::fs::write(manifest_path, self.manifest(image_name).to_string(format)?)?;
+        Ok(())
+    }
+
+    /// Upload the pixels into a [Texture] owned by `assets`
+    pub fn upload(&self, assets: &mut AssetManager) -> Result<TextureAtlas, AtlasError> {
+        let texture = Texture::from_image(&self.image)?;
+        Ok(TextureAtlas {
+            texture: assets.insert(texture),
+            regions: self.regions.clone(),
+        })
+    }
+}
+
+/// An uploaded [Atlas]
+#[derive(Debug, Clone)]
+pub struct TextureAtlas {
+    texture: Handle<Texture>,
+    regions: BTreeMap<String, Rectangle>,
+}
+
+impl TextureAtlas {
+    pub fn texture(&self) -> &Handle<Texture> {
+        &self.texture
+    }
+
+    pub fn regions(&self) -> &BTreeMap<String, Rectangle> {
+        &self.regions
+    }
+
+    pub fn region(&self, name: &str) -> Option<Rectangle> {
+        self.regions.get(name).copied()
+    }
+
+    pub fn sub_texture(&self, name: &str) -> Option<SubTexture> {
+        Some(SubTexture {
+            texture: self.texture.clone(),
+            region: self.region(name)?,
+        })
+    }
+}
+
+/// A region of a shared [Texture], drawn with [crate::prelude::Draw::draw_sub_texture]
+#[derive(Debug, Clone)]
+pub struct SubTexture {
+    pub texture: Handle<Texture>,
+    pub region: Rectangle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ManifestFormat {
+    Json,
+    Toml,
+}
+
+impl ManifestFormat {
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Serializable description of an [Atlas]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AtlasManifest {
+    pub image: String,
+    pub width: i32,
+    pub height: i32,
+    pub regions: BTreeMap<String, AtlasRegion>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtlasRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl From<Rectangle> for AtlasRegion {
+    fn from(rect: Rectangle) -> Self {
+        Self {
+            x: rect.x as i32,
+            y: rect.y as i32,
+            width: rect.width as i32,
+            height: rect.height as i32,
+        }
+    }
+}
+
+impl From<AtlasRegion> for Rectangle {
+    fn from(region: AtlasRegion) -> Self {
+        Rectangle::new(
+            region.x as f32,
+            region.y as f32,
+            region.width as f32,
+            region.height as f32,
+        )
+    }
+}
+
+impl AtlasManifest {
+    pub fn to_string(&self, format: ManifestFormat) -> Result<String, AtlasError> {
+        match format {
+            ManifestFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|err| AtlasError::Manifest(err.to_string())),
+            ManifestFormat::Toml => {
+                toml::to_string(self).map_err(|err| AtlasError::Manifest(err.to_string()))
+            }
+        }
+    }
+
+    pub fn parse(text: &str, format: ManifestFormat) -> Result<Self, AtlasError> {
+        match format {
+            ManifestFormat::Json => {
+                serde_json::from_str(text).map_err(|err| AtlasError::Manifest(err.to_string()))
+            }
+            ManifestFormat::Toml => {
+                toml::from_str(text).map_err(|err| AtlasError::Manifest(err.to_string()))
+            }
+        }
+    }
+
+    /// Read a manifest, the format is picked from the file extension
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AtlasError> {
+        let path = path.as_ref();
+        let format = ManifestFormat::from_path(path)
+            .ok_or_else(|| AtlasError::UnknownManifest(path.display().to_string()))?;
+        Self::parse(&std::fs::read_to_string(path)?, format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: i32, color: Color) -> Image {
+        Image::new(size, size, color)
+    }
+
+    fn overlaps(a: Rectangle, b: Rectangle) -> bool {
+        a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+    }
+
+    #[test]
+    fn packs_without_overlap() {
+        let mut builder = AtlasBuilder::new().padding(1);
+        for i in 0..20 {
+            builder.add(
+                format!("sprite{i}"),
+                Image::new(4 + i % 5 * 3, 3 + i % 7 * 2, Color::new(i as u8, 0, 0, 255)),
+            );
+        }
+        let atlas = builder.build().unwrap();
+        let regions: Vec<_> = atlas.regions().iter().collect();
+        assert_eq!(regions.len(), 20);
+
+        for (i, (name, a)) in regions.iter().enumerate() {
+            assert!(a.x >= 1.0 && a.y >= 1.0);
+            assert!(a.x + a.width < atlas.image().width() as f32);
+            assert!(a.y + a.height < atlas.image().height() as f32);
+            // Pixels ended up inside their own region
+            let index: u8 = name.trim_start_matches("sprite").parse().unwrap();
+            assert_eq!(
+                atlas.image().get_pixel(a.x as i32, a.y as i32).unwrap().r,
+                index
+            );
+            for (_, b) in &regions[i + 1..] {
+                let padded = Rectangle::new(b.x - 1.0, b.y - 1.0, b.width + 2.0, b.height + 2.0);
+                assert!(!overlaps(**a, padded), "{a:?} overlaps {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn extrudes_edges_and_rounds_to_power_of_two() {
+        let mut image = square(2, Color::new(10, 0, 0, 255));
+        image.set_pixel(1, 1, Color::new(20, 0, 0, 255));
+        let atlas = AtlasBuilder::new()
+            .extrude(2)
+            .power_of_two(true)
+            .with("a", image)
+            .build()
+            .unwrap();
+        assert_eq!(atlas.image().size().x, 8);
+        assert_eq!(atlas.image().size().y, 8);
+        assert_eq!(atlas.region("a"), Some(Rectangle::new(2.0, 2.0, 2.0, 2.0)));
+        assert_eq!(atlas.image().get_pixel(0, 0).unwrap().r, 10);
+        assert_eq!(atlas.image().get_pixel(5, 5).unwrap().r, 20);
+        assert_eq!(atlas.image().get_pixel(6, 6).unwrap().a, 0);
+    }
+
+    #[test]
+    fn rejects_duplicates_and_oversized_images() {
+        let builder = AtlasBuilder::new()
+            .with("a", square(2, Color::new(0, 0, 0, 255)))
+            .with("a", square(2, Color::new(0, 0, 0, 255)));
+        assert!(matches!(builder.build(), Err(AtlasError::DuplicateName(_))));
+
+        let builder = AtlasBuilder::new()
+            .max_size(16)
+            .with("big", square(17, Color::new(0, 0, 0, 255)));
+        assert!(matches!(builder.build(), Err(AtlasError::TooLarge(16))));
+
+        let mut builder = AtlasBuilder::new().max_size(16);
+        for i in 0..5 {
+            builder.add(i.to_string(), square(8, Color::new(0, 0, 0, 255)));
+        }
+        assert!(matches!(builder.build(), Err(AtlasError::TooLarge(16))));
+
+        let missing = AtlasBuilder::new().with_vfs_file("a", &Vfs::empty(), "a.png");
+        assert!(matches!(missing, Err(AtlasError::Io(_))));
+
+        let builder = AtlasBuilder::new().max_size(0);
+        assert!(matches!(builder.build(), Err(AtlasError::TooLarge(0))));
+        let builder = AtlasBuilder::new().padding(4).max_size(4);
+        assert!(matches!(builder.build(), Err(AtlasError::TooLarge(4))));
+    }
+
+    #[test]
+    fn manifest_roundtrips() {
+        let atlas = AtlasBuilder::new()
+            .padding(2)
+            .with("bunny", square(3, Color::new(1, 2, 3, 255)))
+            .with("coin", square(5, Color::new(4, 5, 6, 255)))
+            .build()
+            .unwrap();
+        let manifest = atlas.manifest("sprites.png");
+        for format in [ManifestFormat::Json, ManifestFormat::Toml] {
+            let text = manifest.to_string(format).unwrap();
+            assert_eq!(AtlasManifest::parse(&text, format).unwrap(), manifest);
+        }
+
+        let restored = Atlas::from_manifest(atlas.image().clone(), &manifest);
+        assert_eq!(restored.regions(), atlas.regions());
+    }
+}
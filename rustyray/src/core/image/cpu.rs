@@ -0,0 +1,832 @@
+use std::ffi::{CString, c_void};
+use std::path::Path;
+
+use rustyray_sys::consts::PixelFormat;
+use rustyray_sys::ffi::{load_image_from_memory, unload_image};
+use rustyray_sys::texture::Image as RayImage;
+use thiserror::Error;
+
+use crate::core::assets::{Asset, AssetLoader, LoadContext};
+use crate::core::color::Color;
+use crate::core::math::{Rectangle, Vector2i};
+
+#[derive(Debug, Error)]
+pub enum ImageError {
+    #[error("file not found: {0}")]
+    FileNotFound(String),
+    #[error("failed to decode image")]
+    DecodeFailed,
+    #[error("compressed pixel format {0:?} is not supported on the cpu")]
+    UnsupportedFormat(PixelFormat),
+    #[error("expected {expected} bytes of pixel data, got {actual}")]
+    SizeMismatch { expected: usize, actual: usize },
+    #[error("region is outside of the image")]
+    OutOfBounds,
+}
+
+/// Sampling used by [Image::resize]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResizeFilter {
+    /// Pick the closest pixel, keeps pixel art sharp
+    Nearest,
+    /// Blend the four closest pixels
+    #[default]
+    Bilinear,
+}
+
+/// Pixels stored in CPU memory (RAM), in any uncompressed [PixelFormat]
+///
+/// Operations on formats other than [PixelFormat::UncompressedR8G8B8A8] go through an 8 bit RGBA
+/// copy and are converted back afterwards, like raylib does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    width: i32,
+    height: i32,
+    format: PixelFormat,
+    data: Vec<u8>,
+}
+
+/// Bytes used by one pixel of an uncompressed format
+fn pixel_size(format: PixelFormat) -> Result<usize, ImageError> {
+    format
+        .bytes_per_pixel()
+        .ok_or(ImageError::UnsupportedFormat(format))
+}
+
+fn gray(color: Color) -> u8 {
+    (color.r as f32 * 0.299 + color.g as f32 * 0.587 + color.b as f32 * 0.114).round() as u8
+}
+
+fn unorm(value: u8) -> f32 {
+    value as f32 / 255.0
+}
+
+fn to_unorm(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Scale a `bits` wide channel to 8 bits and back
+fn expand(value: u16, bits: u32) -> u8 {
+    let max = (1u16 << bits) - 1;
+    ((value as u32 * 255 + max as u32 / 2) / max as u32) as u8
+}
+
+fn shrink(value: u8, bits: u32) -> u16 {
+    let max = (1u32 << bits) - 1;
+    ((value as u32 * max + 127) / 255) as u16
+}
+
+fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half >> 15) as u32) << 31;
+    let exponent = ((half >> 10) & 0x1f) as u32;
+    let mantissa = (half & 0x3ff) as u32;
+    let bits = match (exponent, mantissa) {
+        (0, 0) => sign,
+        (0, _) => {
+            // Subnormal, normalize it
+            let shift = mantissa.leading_zeros() - 21;
+            sign | ((113 - shift) << 23) | ((mantissa << shift) & 0x3ff) << 13
+        }
+        (0x1f, _) => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 112) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(bits)
+}
+
+fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32 - 112;
+    let mantissa = bits & 0x7f_ffff;
+    if exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if exponent <= 0 {
+        return sign;
+    }
+    // Round to nearest, carrying into the exponent is fine
+    sign | (((exponent as u32) << 10 | mantissa >> 13) + ((mantissa >> 12) & 1)) as u16
+}
+
+fn read_u16(bytes: &[u8]) -> u16 {
+    u16::from_ne_bytes([bytes[0], bytes[1]])
+}
+
+fn read_f32(bytes: &[u8]) -> f32 {
+    f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn decode_pixel(format: PixelFormat, bytes: &[u8]) -> Color {
+    use PixelFormat::*;
+    match format {
+        UncompressedGrayscale => Color::new(bytes[0], bytes[0], bytes[0], 255),
+        UncompressedGrayAlpha => Color::new(bytes[0], bytes[0], bytes[0], bytes[1]),
+        UncompressedR5G6B5 => {
+            let value = read_u16(bytes);
+            Color::new(
+                expand(value >> 11, 5),
+                expand((value >> 5) & 0x3f, 6),
+                expand(value & 0x1f, 5),
+                255,
+            )
+        }
+        UncompressedR8G8B8 => Color::new(bytes[0], bytes[1], bytes[2], 255),
+        UncompressedR5G5B5A1 => {
+            let value = read_u16(bytes);
+            Color::new(
+                expand(value >> 11, 5),
+                expand((value >> 6) & 0x1f, 5),
+                expand((value >> 1) & 0x1f, 5),
+                if value & 1 == 1 { 255 } else { 0 },
+            )
+        }
+        UncompressedR4G4B4A4 => {
+            let value = read_u16(bytes);
+            Color::new(
+                expand(value >> 12, 4),
+                expand((value >> 8) & 0xf, 4),
+                expand((value >> 4) & 0xf, 4),
+                expand(value & 0xf, 4),
+            )
+        }
+        UncompressedR8G8B8A8 => Color::new(bytes[0], bytes[1], bytes[2], bytes[3]),
+        UncompressedR32 => {
+            let value = to_unorm(read_f32(bytes));
+            Color::new(value, value, value, 255)
+        }
+        UncompressedR32G32B32 | UncompressedR32G32B32A32 => {
+            let channel = |index: usize| to_unorm(read_f32(&bytes[index * 4..]));
+            let alpha = if format == UncompressedR32G32B32A32 {
+                channel(3)
+            } else {
+                255
+            };
+            Color::new(channel(0), channel(1), channel(2), alpha)
+        }
+        UncompressedR16 => {
+            let value = to_unorm(f16_to_f32(read_u16(bytes)));
+            Color::new(value, value, value, 255)
+        }
+        UncompressedR16G16B16 | UncompressedR16G16B16A16 => {
+            let channel = |index: usize| to_unorm(f16_to_f32(read_u16(&bytes[index * 2..])));
+            let alpha = if format == UncompressedR16G16B16A16 {
+                channel(3)
+            } else {
+                255
+            };
+            Color::new(channel(0), channel(1), channel(2), alpha)
+        }
+        _ => unreachable!("compressed formats are rejected on construction"),
+    }
+}
+
+fn encode_pixel(format: PixelFormat, color: Color, out: &mut Vec<u8>) {
+    use PixelFormat::*;
+    // raylib's PIXELFORMAT_UNCOMPRESSED_R5G5B5A1_ALPHA_THRESHOLD
+    const ALPHA_THRESHOLD: u8 = 50;
+    match format {
+        UncompressedGrayscale => out.push(gray(color)),
+        UncompressedGrayAlpha => out.extend([gray(color), color.a]),
+        UncompressedR5G6B5 => out.extend(
+            (shrink(color.r, 5) << 11 | shrink(color.g, 6) << 5 | shrink(color.b, 5)).to_ne_bytes(),
+        ),
+        UncompressedR8G8B8 => out.extend([color.r, color.g, color.b]),
+        UncompressedR5G5B5A1 => out.extend(
+            (shrink(color.r, 5) << 11
+                | shrink(color.g, 5) << 6
+                | shrink(color.b, 5) << 1
+                | (color.a > ALPHA_THRESHOLD) as u16)
+                .to_ne_bytes(),
+        ),
+        UncompressedR4G4B4A4 => out.extend(
+            (shrink(color.r, 4) << 12
+                | shrink(color.g, 4) << 8
+                | shrink(color.b, 4) << 4
+                | shrink(color.a, 4))
+            .to_ne_bytes(),
+        ),
+        UncompressedR8G8B8A8 => out.extend([color.r, color.g, color.b, color.a]),
+        UncompressedR32 => out.extend(unorm(gray(color)).to_ne_bytes()),
+        UncompressedR32G32B32 => {
+            for channel in [color.r, color.g, color.b] {
+                out.extend(unorm(channel).to_ne_bytes());
+            }
+        }
+        UncompressedR32G32B32A32 => {
+            for channel in [color.r, color.g, color.b, color.a] {
+                out.extend(unorm(channel).to_ne_bytes());
+            }
+        }
+        UncompressedR16 => out.extend(f32_to_f16(unorm(gray(color))).to_ne_bytes()),
+        UncompressedR16G16B16 => {
+            for channel in [color.r, color.g, color.b] {
+                out.extend(f32_to_f16(unorm(channel)).to_ne_bytes());
+            }
+        }
+        UncompressedR16G16B16A16 => {
+            for channel in [color.r, color.g, color.b, color.a] {
+                out.extend(f32_to_f16(unorm(channel)).to_ne_bytes());
+            }
+        }
+        _ => unreachable!("compressed formats are rejected on construction"),
+    }
+}
+
+fn lerp(a: u8, b: u8, t: f32) -> f32 {
+    a as f32 + (b as f32 - a as f32) * t
+}
+
+fn lerp_color(a: Color, b: Color, t: f32) -> [f32; 4] {
+    [
+        lerp(a.r, b.r, t),
+        lerp(a.g, b.g, t),
+        lerp(a.b, b.b, t),
+        lerp(a.a, b.a, t),
+    ]
+}
+
+impl Image {
+    /// An 8 bit RGBA image filled with `color`
+    pub fn new(width: i32, height: i32, color: Color) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        Self::from_colors_unchecked(width, height, vec![color; width as usize * height as usize])
+    }
+
+    /// An 8 bit RGBA image from `width * height` row-major pixels
+    pub fn from_colors(width: i32, height: i32, pixels: Vec<Color>) -> Result<Self, ImageError> {
+        let (width, height) = (width.max(0), height.max(0));
+        let expected = width as usize * height as usize;
+        if pixels.len() != expected {
+            return Err(ImageError::SizeMismatch {
+                expected: expected * 4,
+                actual: pixels.len() * 4,
+            });
+        }
+        Ok(Self::from_colors_unchecked(width, height, pixels))
+    }
+
+    fn from_colors_unchecked(width: i32, height: i32, pixels: Vec<Color>) -> Self {
+        let mut data = Vec::with_capacity(pixels.len() * 4);
+        for color in pixels {
+            data.extend([color.r, color.g, color.b, color.a]);
+        }
+        Self {
+            width,
+            height,
+            format: PixelFormat::UncompressedR8G8B8A8,
+            data,
+        }
+    }
+
+    /// Wrap raw pixel data laid out in `format`, multi-byte channels use native endianness
+    pub fn from_raw(
+        width: i32,
+        height: i32,
+        format: PixelFormat,
+        data: Vec<u8>,
+    ) -> Result<Self, ImageError> {
+        let expected = width.max(0) as usize * height.max(0) as usize * pixel_size(format)?;
+        if data.len() != expected {
+            return Err(ImageError::SizeMismatch {
+                expected,
+                actual: data.len(),
+            });
+        }
+        Ok(Self {
+            width: width.max(0),
+            height: height.max(0),
+            format,
+            data,
+        })
+    }
+
+    /// Decode an encoded image file, `extension` picks the decoder, e.g. `png`
+    ///
+    /// Safe to call on any thread, no window is needed.
+    pub fn from_memory(bytes: &[u8], extension: &str) -> Result<Self, ImageError> {
+        let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+        let extension =
+            CString::new(format!(".{extension}")).map_err(|_| ImageError::DecodeFailed)?;
+        let size = i32::try_from(bytes.len()).map_err(|_| ImageError::DecodeFailed)?;
+        let image = unsafe { load_image_from_memory(extension.as_ptr(), bytes.as_ptr(), size) };
+        Self::from_ray(image)
+    }
+
+    /// Read and decode an image file, the extension picks the decoder
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ImageError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|_| ImageError::FileNotFound(path.display().to_string()))?;
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("png");
+        Self::from_memory(&bytes, extension)
+    }
+
+    /// Copy an image allocated by raylib and unload it
+    pub(crate) fn from_ray(image: RayImage) -> Result<Self, ImageError> {
+        if image.data.is_null() || image.width <= 0 || image.height <= 0 {
+            return Err(ImageError::DecodeFailed);
+        }
+
+        let result = PixelFormat::try_from(image.format)
+            .map_err(|_| ImageError::DecodeFailed)
+            .and_then(|format| {
+                let len = image.width as usize * image.height as usize * pixel_size(format)?;
+                // Only the first mipmap level is kept
+                let data =
+                    unsafe { std::slice::from_raw_parts(image.data as *const u8, len) }.to_vec();
+                Self::from_raw(image.width, image.height, format, data)
+            });
+        unsafe { unload_image(image) };
+        result
+    }
+
+    /// Borrow the pixels as a raylib image, raylib must only read from it
+    pub(crate) fn as_ray(&self) -> RayImage {
+        RayImage {
+            data: self.data.as_ptr() as *mut c_void,
+            width: self.width,
+            height: self.height,
+            mipmaps: 1,
+            format: self.format as i32,
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn size(&self) -> Vector2i {
+        Vector2i::new(self.width, self.height)
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    /// Raw pixel data laid out in [Image::format]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    /// Pixels as [Color]s without copying, `None` unless the format is 8 bit RGBA
+    pub fn as_colors(&self) -> Option<&[Color]> {
+        (self.format == PixelFormat::UncompressedR8G8B8A8).then(|| {
+            // SAFETY: Color is repr(C) with four u8 fields, so it has the size and alignment of [u8; 4]
+            unsafe {
+                std::slice::from_raw_parts(self.data.as_ptr() as *const Color, self.data.len() / 4)
+            }
+        })
+    }
+
+    pub fn as_colors_mut(&mut self) -> Option<&mut [Color]> {
+        (self.format == PixelFormat::UncompressedR8G8B8A8).then(|| {
+            // SAFETY: see `as_colors`
+            unsafe {
+                std::slice::from_raw_parts_mut(
+                    self.data.as_mut_ptr() as *mut Color,
+                    self.data.len() / 4,
+                )
+            }
+        })
+    }
+
+    /// Copy of every pixel converted to [Color], row-major
+    pub fn to_colors(&self) -> Vec<Color> {
+        if let Some(colors) = self.as_colors() {
+            return colors.to_vec();
+        }
+        let size = pixel_size(self.format).unwrap();
+        self.data
+            .chunks_exact(size)
+            .map(|bytes| decode_pixel(self.format, bytes))
+            .collect()
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        (x >= 0 && y >= 0 && x < self.width && y < self.height)
+            .then(|| y as usize * self.width as usize + x as usize)
+    }
+
+    pub fn get_pixel(&self, x: i32, y: i32) -> Option<Color> {
+        let size = pixel_size(self.format).unwrap();
+        let start = self.index(x, y)? * size;
+        Some(decode_pixel(self.format, &self.data[start..start + size]))
+    }
+
+    /// Set a pixel, out of bounds coordinates are ignored
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: Color) {
+        let Some(index) = self.index(x, y) else {
+            return;
+        };
+        let size = pixel_size(self.format).unwrap();
+        let mut bytes = Vec::with_capacity(size);
+        encode_pixel(self.format, color, &mut bytes);
+        self.data[index * size..(index + 1) * size].copy_from_slice(&bytes);
+    }
+
+    /// Replace the pixels with `width * height` colors, encoded in the current format
+    fn store_colors(&mut self, width: i32, height: i32, pixels: Vec<Color>) {
+        let format = self.format;
+        *self = Self::from_colors_unchecked(width, height, pixels);
+        if format != PixelFormat::UncompressedR8G8B8A8 {
+            self.convert(format);
+        }
+    }
+
+    fn map_colors(&mut self, mut map: impl FnMut(Color) -> Color) {
+        if let Some(colors) = self.as_colors_mut() {
+            colors.iter_mut().for_each(|color| *color = map(*color));
+            return;
+        }
+        let pixels = self.to_colors().into_iter().map(map).collect();
+        self.store_colors(self.width, self.height, pixels);
+    }
+
+    /// Re-encode the pixels in `format`
+    ///
+    /// # Panics
+    /// When `format` is compressed, those can only be loaded from files.
+    pub fn convert(&mut self, format: PixelFormat) {
+        let size = pixel_size(format).expect("can not convert to a compressed pixel format");
+        if format == self.format {
+            return;
+        }
+        let mut data =
+            Vec::with_capacity(self.data.len() / pixel_size(self.format).unwrap() * size);
+        for color in self.to_colors() {
+            encode_pixel(format, color, &mut data);
+        }
+        self.format = format;
+        self.data = data;
+    }
+
+    pub fn converted(&self, format: PixelFormat) -> Self {
+        let mut image = self.clone();
+        image.convert(format);
+        image
+    }
+
+    /// Keep only the pixels inside `region`, which must be within the image
+    pub fn crop(&mut self, region: Rectangle) -> Result<(), ImageError> {
+        let (x, y) = (region.x as i32, region.y as i32);
+        let (width, height) = (region.width as i32, region.height as i32);
+        if x < 0
+            || y < 0
+            || width <= 0
+            || height <= 0
+            || x + width > self.width
+            || y + height > self.height
+        {
+            return Err(ImageError::OutOfBounds);
+        }
+
+        let size = pixel_size(self.format).unwrap();
+        let row = self.width as usize * size;
+        let mut data = Vec::with_capacity(width as usize * height as usize * size);
+        for line in y..y + height {
+            let start = line as usize * row + x as usize * size;
+            data.extend_from_slice(&self.data[start..start + width as usize * size]);
+        }
+        self.width = width;
+        self.height = height;
+        self.data = data;
+        Ok(())
+    }
+
+    /// Scale to `width` x `height`, non-positive sizes leave the image unchanged
+    pub fn resize(&mut self, width: i32, height: i32, filter: ResizeFilter) {
+        if width <= 0 || height <= 0 {
+            return;
+        }
+        let len = width as usize * height as usize;
+        let source = self.to_colors();
+        let (src_width, src_height) = (self.width, self.height);
+        if src_width == 0 || src_height == 0 {
+            self.store_colors(width, height, vec![Color::new(0, 0, 0, 0); len]);
+            return;
+        }
+
+        let at = |x: i32, y: i32| {
+            let (x, y) = (x.clamp(0, src_width - 1), y.clamp(0, src_height - 1));
+            source[y as usize * src_width as usize + x as usize]
+        };
+        let scale_x = src_width as f32 / width.max(1) as f32;
+        let scale_y = src_height as f32 / height.max(1) as f32;
+
+        let mut pixels = Vec::with_capacity(len);
+        for y in 0..height {
+            for x in 0..width {
+                let color = match filter {
+                    ResizeFilter::Nearest => {
+                        at((x as f32 * scale_x) as i32, (y as f32 * scale_y) as i32)
+                    }
+                    ResizeFilter::Bilinear => {
+                        let sx = ((x as f32 + 0.5) * scale_x - 0.5).max(0.0);
+                        let sy = ((y as f32 + 0.5) * scale_y - 0.5).max(0.0);
+                        let (x0, y0) = (sx.floor() as i32, sy.floor() as i32);
+                        let (tx, ty) = (sx.fract(), sy.fract());
+                        let top = lerp_color(at(x0, y0), at(x0 + 1, y0), tx);
+                        let bottom = lerp_color(at(x0, y0 + 1), at(x0 + 1, y0 + 1), tx);
+                        let channel = |index: usize| {
+                            (top[index] + (bottom[index] - top[index]) * ty).round() as u8
+                        };
+                        Color::new(channel(0), channel(1), channel(2), channel(3))
+                    }
+                };
+                pixels.push(color);
+            }
+        }
+        self.store_colors(width, height, pixels);
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_vertical(&mut self) {
+        let row = self.width as usize * pixel_size(self.format).unwrap();
+        if row == 0 {
+            return;
+        }
+        let mut data = Vec::with_capacity(self.data.len());
+        for line in self.data.chunks_exact(row).rev() {
+            data.extend_from_slice(line);
+        }
+        self.data = data;
+    }
+
+    /// Mirror left to right
+    pub fn flip_horizontal(&mut self) {
+        let size = pixel_size(self.format).unwrap();
+        let row = self.width as usize * size;
+        if row == 0 {
+            return;
+        }
+        for line in self.data.chunks_exact_mut(row) {
+            let mut pixels: Vec<&[u8]> = line.chunks_exact(size).collect();
+            pixels.reverse();
+            let flipped = pixels.concat();
+            line.copy_from_slice(&flipped);
+        }
+    }
+
+    /// Rotate 90 degrees clockwise
+    pub fn rotate_cw(&mut self) {
+        let source = self.to_colors();
+        let (width, height) = (self.width, self.height);
+        let mut pixels = Vec::with_capacity(source.len());
+        for y in 0..width {
+            for x in 0..height {
+                pixels.push(source[(height - 1 - x) as usize * width as usize + y as usize]);
+            }
+        }
+        self.store_colors(height, width, pixels);
+    }
+
+    /// Rotate 90 degrees counter-clockwise
+    pub fn rotate_ccw(&mut self) {
+        let source = self.to_colors();
+        let (width, height) = (self.width, self.height);
+        let mut pixels = Vec::with_capacity(source.len());
+        for y in 0..width {
+            for x in 0..height {
+                pixels.push(source[x as usize * width as usize + (width - 1 - y) as usize]);
+            }
+        }
+        self.store_colors(height, width, pixels);
+    }
+
+    /// Rotate clockwise by any angle, the image grows to fit and new pixels are transparent
+    pub fn rotate(&mut self, degrees: f32) {
+        let turns = degrees.rem_euclid(360.0);
+        if turns == 0.0 {
+            return;
+        } else if turns == 90.0 {
+            return self.rotate_cw();
+        } else if turns == 270.0 {
+            return self.rotate_ccw();
+        } else if turns == 180.0 {
+            self.flip_vertical();
+            return self.flip_horizontal();
+        }
+
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (src_width, src_height) = (self.width as f32, self.height as f32);
+        let width = (src_width * cos.abs() + src_height * sin.abs()).round() as i32;
+        let height = (src_width * sin.abs() + src_height * cos.abs()).round() as i32;
+        let source = self.to_colors();
+
+        let mut pixels = Vec::with_capacity(width.max(0) as usize * height.max(0) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                // Map back from the destination center into the source
+                let dx = x as f32 + 0.5 - width as f32 / 2.0;
+                let dy = y as f32 + 0.5 - height as f32 / 2.0;
+                let sx = (dx * cos + dy * sin + src_width / 2.0).floor() as i32;
+                let sy = (-dx * sin + dy * cos + src_height / 2.0).floor() as i32;
+                let inside = sx >= 0 && sy >= 0 && sx < self.width && sy < self.height;
+                pixels.push(if inside {
+                    source[sy as usize * self.width as usize + sx as usize]
+                } else {
+                    Color::new(0, 0, 0, 0)
+                });
+            }
+        }
+        self.store_colors(width, height, pixels);
+    }
+
+    /// Multiply every pixel with `color`
+    pub fn tint(&mut self, color: Color) {
+        self.map_colors(|mut pixel| *pixel.tint(&color));
+    }
+
+    /// Replace every pixel equal to `from` with `to`
+    pub fn replace_color(&mut self, from: Color, to: Color) {
+        self.map_colors(|pixel| if pixel == from { to } else { pixel });
+    }
+
+    /// Multiply the color channels with alpha
+    pub fn premultiply_alpha(&mut self) {
+        self.map_colors(|pixel| {
+            let scale = |channel: u8| ((channel as u32 * pixel.a as u32 + 127) / 255) as u8;
+            Color::new(scale(pixel.r), scale(pixel.g), scale(pixel.b), pixel.a)
+        });
+    }
+}
+
+impl Asset for Image {
+    fn size_estimate(&self) -> usize {
+        self.data.len()
+    }
+}
+
+/// Image files decoded into CPU memory, keep a [crate::core::image::Texture] for drawing
+impl AssetLoader for Image {
+    type Key = String;
+    type Error = ImageError;
+    type Decoded = Image;
+
+    async fn decode(path: Self::Key, ctx: LoadContext) -> Result<Image, Self::Error> {
+        let bytes = ctx
+            .read(&path)
+            .await
+            .map_err(|_| ImageError::FileNotFound(path.clone()))?;
+        let extension = Path::new(&path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("png");
+        Image::from_memory(&bytes, extension)
+    }
+
+    fn finish(decoded: Image) -> Result<Self, Self::Error> {
+        Ok(decoded)
+    }
+
+    fn path(path: &Self::Key) -> Option<&Path> {
+        Some(Path::new(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const R: Color = Color::new(255, 0, 0, 255);
+    const G: Color = Color::new(0, 255, 0, 255);
+    const B: Color = Color::new(0, 0, 255, 255);
+    const W: Color = Color::new(255, 255, 255, 255);
+
+    /// 2x2 image:
+    /// R G
+    /// B W
+    fn quad() -> Image {
+        Image::from_colors(2, 2, vec![R, G, B, W]).unwrap()
+    }
+
+    #[test]
+    fn from_colors_checks_size() {
+        assert!(matches!(
+            Image::from_colors(2, 2, vec![R; 3]),
+            Err(ImageError::SizeMismatch { .. })
+        ));
+        assert!(matches!(
+            Image::from_raw(1, 1, PixelFormat::CompressedDxt1Rgb, vec![0; 8]),
+            Err(ImageError::UnsupportedFormat(_))
+        ));
+        let empty = Image::from_colors(-3, 2, Vec::new()).unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 2));
+    }
+
+    #[test]
+    fn pixels_are_accessible() {
+        let mut image = quad();
+        assert_eq!(image.get_pixel(1, 0), Some(G));
+        assert_eq!(image.get_pixel(2, 0), None);
+        image.set_pixel(0, 1, W);
+        image.set_pixel(-1, 0, W);
+        assert_eq!(image.as_colors().unwrap(), [R, G, W, W]);
+    }
+
+    #[test]
+    fn crop_keeps_region() {
+        let mut image = quad();
+        image.crop(Rectangle::new(1.0, 0.0, 1.0, 2.0)).unwrap();
+        assert_eq!(image.size(), Vector2i::new(1, 2));
+        assert_eq!(image.to_colors(), [G, W]);
+        assert!(matches!(
+            image.crop(Rectangle::new(0.0, 1.0, 1.0, 2.0)),
+            Err(ImageError::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn resize_nearest_and_bilinear() {
+        let mut nearest = quad();
+        nearest.resize(4, 4, ResizeFilter::Nearest);
+        assert_eq!(nearest.to_colors()[..4], [R, R, G, G]);
+        assert_eq!(nearest.get_pixel(3, 3), Some(W));
+
+        let mut bilinear = Image::from_colors(2, 1, vec![Color::new(0, 0, 0, 255), W]).unwrap();
+        bilinear.resize(4, 1, ResizeFilter::Bilinear);
+        let reds: Vec<u8> = bilinear.to_colors().iter().map(|color| color.r).collect();
+        assert_eq!(reds, [0, 64, 191, 255]);
+
+        bilinear.resize(0, -4, ResizeFilter::Bilinear);
+        assert_eq!((bilinear.width(), bilinear.height()), (4, 1));
+    }
+
+    #[test]
+    fn flips_and_rotations() {
+        let mut image = quad();
+        image.flip_horizontal();
+        assert_eq!(image.to_colors(), [G, R, W, B]);
+        image.flip_vertical();
+        assert_eq!(image.to_colors(), [W, B, G, R]);
+
+        let mut image = Image::from_colors(2, 1, vec![R, G]).unwrap();
+        image.rotate_cw();
+        assert_eq!(image.size(), Vector2i::new(1, 2));
+        assert_eq!(image.to_colors(), [R, G]);
+        image.rotate_cw();
+        assert_eq!(image.to_colors(), [G, R]);
+        image.rotate_ccw();
+        image.rotate(-90.0);
+        assert_eq!(image.to_colors(), [R, G]);
+
+        let mut image = Image::new(10, 10, W);
+        image.rotate(45.0);
+        assert_eq!(image.size(), Vector2i::new(14, 14));
+        assert_eq!(image.get_pixel(7, 7), Some(W));
+        assert_eq!(image.get_pixel(0, 0), Some(Color::new(0, 0, 0, 0)));
+    }
+
+    #[test]
+    fn color_operations() {
+        let mut image = quad();
+        image.replace_color(W, Color::new(255, 255, 255, 128));
+        image.tint(Color::new(255, 128, 255, 255));
+        image.premultiply_alpha();
+        assert_eq!(image.get_pixel(1, 0), Some(Color::new(0, 128, 0, 255)));
+        assert_eq!(image.get_pixel(1, 1), Some(Color::new(128, 64, 128, 128)));
+    }
+
+    #[test]
+    fn format_conversions_roundtrip() {
+        use PixelFormat::*;
+        let image = Image::from_colors(2, 1, vec![Color::new(255, 128, 0, 255), R]).unwrap();
+        for format in [
+            UncompressedR8G8B8,
+            UncompressedR32G32B32A32,
+            UncompressedR16G16B16A16,
+        ] {
+            let converted = image.converted(format);
+            assert_eq!(converted.data().len(), 2 * pixel_size(format).unwrap());
+            assert_eq!(converted.to_colors(), image.to_colors(), "{format:?}");
+        }
+
+        let packed = image.converted(UncompressedR5G6B5);
+        assert_eq!(packed.get_pixel(0, 0), Some(Color::new(255, 130, 0, 255)));
+
+        let mut gray = Image::new(1, 1, W).converted(UncompressedGrayscale);
+        assert_eq!(gray.data(), [255]);
+        gray.tint(Color::new(128, 128, 128, 255));
+        assert_eq!(gray.format(), UncompressedGrayscale);
+        assert_eq!(gray.data(), [128]);
+    }
+
+    #[test]
+    fn half_floats() {
+        for value in [0.0, 1.0, 0.5, -2.0, 65504.0, 0.000061035156] {
+            assert_eq!(f16_to_f32(f32_to_f16(value)), value);
+        }
+        assert_eq!(f16_to_f32(1), 2f32.powi(-24));
+    }
+}
@@ -0,0 +1,410 @@
+use std::borrow::Cow;
+use std::ffi::{CString, c_void};
+use std::io;
+use std::path::Path;
+
+use rustyray_sys::consts::PixelFormat;
+use rustyray_sys::ffi::{export_image_to_memory, load_image_from_texture, mem_free};
+use thiserror::Error;
+
+use super::{Image, ImageError, RenderTexture};
+
+/// File formats [Image::encode] can write
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFileFormat {
+    Png,
+    /// [The Quite OK Image Format](https://qoiformat.org), lossless and fast to encode
+    Qoi,
+    /// 32 bit BMP with alpha
+    Bmp,
+    /// Headerless 8 bit RGBA pixels, row by row from the top
+    RawRgba,
+}
+
+impl ImageFileFormat {
+    /// Guess the format from the extension of `path`: `png`, `qoi`, `bmp`, `raw` or `rgba`
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        Some(match extension.as_str() {
+            "png" => Self::Png,
+            "qoi" => Self::Qoi,
+            "bmp" => Self::Bmp,
+            "raw" | "rgba" => Self::RawRgba,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ImageExportError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Image(#[from] ImageError),
+    #[error("unknown image file extension: {0}")]
+    UnknownExtension(String),
+    #[error("can not export an empty image")]
+    Empty,
+    #[error("image is too large for {0:?}")]
+    TooLarge(ImageFileFormat),
+    #[error("failed to encode {0:?}")]
+    EncodeFailed(ImageFileFormat),
+}
+
+impl Image {
+    /// Write to `path`, the format is picked from the file extension
+    pub fn export(&self, path: impl AsRef<Path>) -> Result<(), ImageExportError> {
+        let path = path.as_ref();
+        let format = ImageFileFormat::from_path(path)
+            .ok_or_else(|| ImageExportError::UnknownExtension(path.display().to_string()))?;
+        self.export_as(path, format)
+    }
+
+    pub fn export_as(
+        &self,
+        path: impl AsRef<Path>,
+        format: ImageFileFormat,
+    ) -> Result<(), ImageExportError> {
+        std::fs::write(path, self.encode(format)?)?;
+        Ok(())
+    }
+
+    /// Encode into an in-memory file
+    ///
+    /// **NOTE**: PNG is encoded by raylib, the other formats are pure Rust and deterministic.
+    pub fn encode(&self, format: ImageFileFormat) -> Result<Vec<u8>, ImageExportError> {
+        if self.width() == 0 || self.height() == 0 {
+            return Err(ImageExportError::Empty);
+        }
+        match format {
+            ImageFileFormat::Png => self.encode_png(),
+            ImageFileFormat::Qoi => encode_qoi(self),
+            ImageFileFormat::Bmp => encode_bmp(self),
+            ImageFileFormat::RawRgba => Ok(self
+                .converted(PixelFormat::UncompressedR8G8B8A8)
+                .data()
+                .to_vec()),
+        }
+    }
+
+    fn encode_png(&self) -> Result<Vec<u8>, ImageExportError> {
+        // raylib reads any other format as 4 bytes per pixel, past the end of smaller pixels
+        let image = match self.format() {
+            PixelFormat::UncompressedGrayscale
+            | PixelFormat::UncompressedGrayAlpha
+            | PixelFormat::UncompressedR8G8B8
+            | PixelFormat::UncompressedR8G8B8A8 => Cow::Borrowed(self),
+            _ => Cow::Owned(self.converted(PixelFormat::UncompressedR8G8B8A8)),
+        };
+        let file_type = CString::new(".png").unwrap();
+        let mut size = 0;
+        let data = unsafe { export_image_to_memory(image.as_ray(), file_type.as_ptr(), &mut size) };
+        if data.is_null() || size <= 0 {
+            return Err(ImageExportError::EncodeFailed(ImageFileFormat::Png));
+        }
+        let bytes = unsafe { std::slice::from_raw_parts(data, size as usize) }.to_vec();
+        unsafe { mem_free(data as *mut c_void) };
+        Ok(bytes)
+    }
+}
+
+impl RenderTexture {
+    /// Read the pixels back from the GPU into a CPU image, top row first
+    pub fn to_image(&self) -> Result<Image, ImageError> {
+        let mut image = Image::from_ray(unsafe { load_image_from_texture(self.as_ray().texture) })?;
+        // OpenGL stores framebuffers bottom row first
+        image.flip_vertical();
+        Ok(image)
+    }
+
+    /// Read back and write to `path`, the format is picked from the file extension
+    pub fn export(&self, path: impl AsRef<Path>) -> Result<(), ImageExportError> {
+        self.to_image()?.export(path)
+    }
+
+    pub fn encode(&self, format: ImageFileFormat) -> Result<Vec<u8>, ImageExportError> {
+        self.to_image()?.encode(format)
+    }
+}
+
+fn rgba_pixels(image: &Image) -> Vec<[u8; 4]> {
+    image
+        .to_colors()
+        .into_iter()
+        .map(|color| [color.r, color.g, color.b, color.a])
+        .collect()
+}
+
+fn encode_qoi(image: &Image) -> Result<Vec<u8>, ImageExportError> {
+    const OP_INDEX: u8 = 0x00;
+    const OP_DIFF: u8 = 0x40;
+    const OP_LUMA: u8 = 0x80;
+    const OP_RUN: u8 = 0xc0;
+    const OP_RGB: u8 = 0xfe;
+    const OP_RGBA: u8 = 0xff;
+
+    let pixels = rgba_pixels(image);
+    let mut out = Vec::with_capacity(14 + pixels.len() + 8);
+    out.extend_from_slice(b"qoif");
+    out.extend_from_slice(&(image.width() as u32).to_be_bytes());
+    out.extend_from_slice(&(image.height() as u32).to_be_bytes());
+    // 4 channels, sRGB with linear alpha
+    out.extend_from_slice(&[4, 0]);
+
+    let hash = |[r, g, b, a]: [u8; 4]| {
+        (r as usize * 3 + g as usize * 5 + b as usize * 7 + a as usize * 11) % 64
+    };
+    let mut index = [[0u8; 4]; 64];
+    let mut previous = [0, 0, 0, 255];
+    let mut run = 0u8;
+
+    for (position, &pixel) in pixels.iter().enumerate() {
+        if pixel == previous {
+            run += 1;
+            if run == 62 || position + 1 == pixels.len() {
+                out.push(OP_RUN | (run - 1));
+                run = 0;
+            }
+            continue;
+        }
+        if run > 0 {
+            out.push(OP_RUN | (run - 1));
+            run = 0;
+        }
+
+        let slot = hash(pixel);
+        if index[slot] == pixel {
+            out.push(OP_INDEX | slot as u8);
+        } else {
+            index[slot] = pixel;
+            if pixel[3] == previous[3] {
+                let dr = pixel[0].wrapping_sub(previous[0]) as i8;
+                let dg = pixel[1].wrapping_sub(previous[1]) as i8;
+                let db = pixel[2].wrapping_sub(previous[2]) as i8;
+                let (dr_dg, db_dg) = (dr.wrapping_sub(dg), db.wrapping_sub(dg));
+
+                if (-2..=1).contains(&dr) && (-2..=1).contains(&dg) && (-2..=1).contains(&db) {
+                    out.push(
+                        OP_DIFF | ((dr + 2) as u8) << 4 | ((dg + 2) as u8) << 2 | (db + 2) as u8,
+                    );
+                } else if (-32..=31).contains(&dg)
+                    && (-8..=7).contains(&dr_dg)
+                    && (-8..=7).contains(&db_dg)
+                {
+                    out.push(OP_LUMA | (dg + 32) as u8);
+                    out.push(((dr_dg + 8) as u8) << 4 | (db_dg + 8) as u8);
+                } else {
+                    out.extend_from_slice(&[OP_RGB, pixel[0], pixel[1], pixel[2]]);
+                }
+            } else {
+                out.push(OP_RGBA);
+                out.extend_from_slice(&pixel);
+            }
+        }
+        previous = pixel;
+    }
+
+    out.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1]);
+    Ok(out)
+}
+
+fn encode_bmp(image: &Image) -> Result<Vec<u8>, ImageExportError> {
+    const FILE_HEADER: u32 = 14;
+    // BITMAPV4HEADER, needed for the alpha channel mask
+    const INFO_HEADER: u32 = 108;
+
+    let (width, height) = (image.width() as u32, image.height() as u32);
+    let pixel_bytes = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .filter(|size| *size <= u32::MAX - FILE_HEADER - INFO_HEADER)
+        .ok_or(ImageExportError::TooLarge(ImageFileFormat::Bmp))?;
+    let offset = FILE_HEADER + INFO_HEADER;
+
+    let mut out = Vec::with_capacity((offset + pixel_bytes) as usize);
+    out.extend_from_slice(b"BM");
+    out.extend_from_slice(&(offset + pixel_bytes).to_le_bytes());
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(&offset.to_le_bytes());
+
+    out.extend_from_slice(&INFO_HEADER.to_le_bytes());
+    out.extend_from_slice(&(width as i32).to_le_bytes());
+    out.extend_from_slice(&(height as i32).to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // planes
+    out.extend_from_slice(&32u16.to_le_bytes()); // bits per pixel
+    out.extend_from_slice(&3u32.to_le_bytes()); // BI_BITFIELDS
+    out.extend_from_slice(&pixel_bytes.to_le_bytes());
+    out.extend_from_slice(&2835i32.to_le_bytes()); // 72 DPI
+    out.extend_from_slice(&2835i32.to_le_bytes());
+    out.extend_from_slice(&[0; 8]); // palette
+    for mask in [0x00ff_0000u32, 0x0000_ff00, 0x0000_00ff, 0xff00_0000] {
+        out.extend_from_slice(&mask.to_le_bytes());
+    }
+    out.extend_from_slice(b"BGRs"); // LCS_sRGB, little endian
+    out.extend_from_slice(&[0; 36 + 12]); // endpoints and gamma, unused for sRGB
+
+    // Rows are stored bottom to top
+    let pixels = rgba_pixels(image);
+    for row in pixels.chunks_exact(width as usize).rev() {
+        for &[r, g, b, a] in row {
+            out.extend_from_slice(&[b, g, r, a]);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::color::Color;
+
+    fn sample() -> Image {
+        Image::from_colors(
+            3,
+            2,
+            vec![
+                Color::new(255, 0, 0, 255),
+                Color::new(255, 0, 0, 255),
+                Color::new(0, 255, 0, 128),
+                Color::new(1, 1, 1, 255),
+                Color::new(2, 0, 3, 255),
+                Color::new(255, 0, 0, 255),
+            ],
+        )
+        .unwrap()
+    }
+
+    /// Minimal QOI decoder following the specification, to check the encoder
+    fn decode_qoi(bytes: &[u8]) -> (u32, u32, Vec<[u8; 4]>) {
+        assert_eq!(&bytes[..4], b"qoif");
+        let width = u32::from_be_bytes(bytes[4..8].try_into().unwrap());
+        let height = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+        assert_eq!(&bytes[bytes.len() - 8..], [0, 0, 0, 0, 0, 0, 0, 1]);
+
+        let mut pixels = Vec::new();
+        let mut index = [[0u8; 4]; 64];
+        let mut pixel = [0, 0, 0, 255];
+        let mut at = 14;
+        while pixels.len() < (width * height) as usize {
+            let op = bytes[at];
+            at += 1;
+            match op {
+                0xfe => {
+                    pixel[..3].copy_from_slice(&bytes[at..at + 3]);
+                    at += 3;
+                }
+                0xff => {
+                    pixel.copy_from_slice(&bytes[at..at + 4]);
+                    at += 4;
+                }
+                _ => match op >> 6 {
+                    0 => pixel = index[op as usize],
+                    1 => {
+                        pixel[0] = pixel[0].wrapping_add((op >> 4) & 3).wrapping_sub(2);
+                        pixel[1] = pixel[1].wrapping_add((op >> 2) & 3).wrapping_sub(2);
+                        pixel[2] = pixel[2].wrapping_add(op & 3).wrapping_sub(2);
+                    }
+                    2 => {
+                        let dg = (op & 0x3f).wrapping_sub(32);
+                        let next = bytes[at];
+                        at += 1;
+                        pixel[0] = pixel[0]
+                            .wrapping_add(dg)
+                            .wrapping_add(next >> 4)
+                            .wrapping_sub(8);
+                        pixel[1] = pixel[1].wrapping_add(dg);
+                        pixel[2] = pixel[2]
+                            .wrapping_add(dg)
+                            .wrapping_add(next & 0xf)
+                            .wrapping_sub(8);
+                    }
+                    _ => {
+                        for _ in 0..(op & 0x3f) {
+                            pixels.push(pixel);
+                        }
+                    }
+                },
+            }
+            let [r, g, b, a] = pixel;
+            index[(r as usize * 3 + g as usize * 5 + b as usize * 7 + a as usize * 11) % 64] =
+                pixel;
+            pixels.push(pixel);
+        }
+        (width, height, pixels)
+    }
+
+    #[test]
+    fn qoi_roundtrip() {
+        for image in [
+            sample(),
+            Image::gen_perlin_noise(16, 16, 0, 0, 4.0, 1),
+            Image::new(100, 1, Color::BLUE),
+        ] {
+            let (width, height, pixels) = decode_qoi(&image.encode(ImageFileFormat::Qoi).unwrap());
+            assert_eq!(
+                (width, height),
+                (image.width() as u32, image.height() as u32)
+            );
+            assert_eq!(pixels, rgba_pixels(&image));
+        }
+    }
+
+    #[test]
+    fn bmp_layout() {
+        let bytes = sample().encode(ImageFileFormat::Bmp).unwrap();
+        assert_eq!(&bytes[..2], b"BM");
+        assert_eq!(
+            u32::from_le_bytes(bytes[2..6].try_into().unwrap()) as usize,
+            bytes.len()
+        );
+        let offset = u32::from_le_bytes(bytes[10..14].try_into().unwrap()) as usize;
+        assert_eq!(bytes.len(), offset + 3 * 2 * 4);
+        // Bottom row first, stored as BGRA
+        assert_eq!(bytes[offset..offset + 4], [1, 1, 1, 255]);
+        assert_eq!(bytes[offset + 12..offset + 16], [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn raw_and_errors() {
+        let gray = Image::new(2, 1, Color::WHITE).converted(PixelFormat::UncompressedGrayscale);
+        assert_eq!(gray.encode(ImageFileFormat::RawRgba).unwrap(), [255; 8]);
+        assert!(matches!(
+            Image::new(0, 4, Color::WHITE).encode(ImageFileFormat::Qoi),
+            Err(ImageExportError::Empty)
+        ));
+        assert!(matches!(
+            sample().export("capture.jpg"),
+            Err(ImageExportError::UnknownExtension(_))
+        ));
+        assert_eq!(
+            ImageFileFormat::from_path("out/atlas.QOI"),
+            Some(ImageFileFormat::Qoi)
+        );
+    }
+
+    #[test]
+    fn png_roundtrips_non_8_bit_formats() {
+        for format in [
+            PixelFormat::UncompressedR5G6B5,
+            PixelFormat::UncompressedR4G4B4A4,
+            PixelFormat::UncompressedR16G16B16A16,
+        ] {
+            let image = sample().converted(format);
+            let bytes = image.encode(ImageFileFormat::Png).unwrap();
+            let decoded = Image::from_memory(&bytes, "png").unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (3, 2));
+            assert_eq!(decoded.to_colors(), image.to_colors(), "{format:?}");
+        }
+    }
+
+    #[test]
+    fn export_writes_file() {
+        let path = std::env::temp_dir().join(format!("rustyray-{}-export.qoi", std::process::id()));
+        sample().export(&path).unwrap();
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            sample().encode(ImageFileFormat::Qoi).unwrap()
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,430 @@
+use std::f32::consts::{FRAC_1_SQRT_2, SQRT_2};
+
+use crate::core::color::Color;
+
+use super::Image;
+
+/// Small deterministic generator (SplitMix64), the same seed always yields the same image
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0.0..1.0`
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// Permutation table shared by the gradient noises
+struct Permutation([u8; 512]);
+
+impl Permutation {
+    fn new(seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let mut table: [u8; 256] = std::array::from_fn(|index| index as u8);
+        for index in (1..table.len()).rev() {
+            let other = (rng.next_u64() % (index as u64 + 1)) as usize;
+            table.swap(index, other);
+        }
+        Self(std::array::from_fn(|index| table[index % 256]))
+    }
+
+    fn hash(&self, x: i32, y: i32) -> u8 {
+        self.0[self.0[(x & 255) as usize] as usize + (y & 255) as usize]
+    }
+}
+
+const GRADIENTS: [(f32, f32); 8] = [
+    (1.0, 0.0),
+    (-1.0, 0.0),
+    (0.0, 1.0),
+    (0.0, -1.0),
+    (FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+    (-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+];
+
+fn gradient(hash: u8, x: f32, y: f32) -> f32 {
+    let (gx, gy) = GRADIENTS[(hash & 7) as usize];
+    gx * x + gy * y
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Improved Perlin noise, roughly in `-1.0..=1.0`
+fn perlin(perm: &Permutation, x: f32, y: f32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (ix, iy) = (x0 as i32, y0 as i32);
+
+    let corner =
+        |dx: i32, dy: i32| gradient(perm.hash(ix + dx, iy + dy), fx - dx as f32, fy - dy as f32);
+    let (u, v) = (fade(fx), fade(fy));
+    let top = corner(0, 0) + (corner(1, 0) - corner(0, 0)) * u;
+    let bottom = corner(0, 1) + (corner(1, 1) - corner(0, 1)) * u;
+    (top + (bottom - top) * v) * SQRT_2
+}
+
+/// 2D simplex noise, roughly in `-1.0..=1.0`
+fn simplex(perm: &Permutation, x: f32, y: f32) -> f32 {
+    const F2: f32 = 0.366_025_4; // (sqrt(3) - 1) / 2
+    const G2: f32 = 0.211_324_87; // (3 - sqrt(3)) / 6
+
+    let skew = (x + y) * F2;
+    let (i, j) = ((x + skew).floor(), (y + skew).floor());
+    let unskew = (i + j) * G2;
+    let (x0, y0) = (x - (i - unskew), y - (j - unskew));
+    let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+    let (x1, y1) = (x0 - i1 as f32 + G2, y0 - j1 as f32 + G2);
+    let (x2, y2) = (x0 - 1.0 + 2.0 * G2, y0 - 1.0 + 2.0 * G2);
+    let (i, j) = (i as i32, j as i32);
+
+    let contribution = |hash: u8, x: f32, y: f32| {
+        let t = 0.5 - x * x - y * y;
+        if t < 0.0 {
+            0.0
+        } else {
+            t * t * t * t * gradient(hash, x, y)
+        }
+    };
+    70.0 * (contribution(perm.hash(i, j), x0, y0)
+        + contribution(perm.hash(i + i1, j + j1), x1, y1)
+        + contribution(perm.hash(i + 1, j + 1), x2, y2))
+}
+
+/// Fractal sum of `octaves` layers of `noise`, like raylib's `stb_perlin_fbm_noise3`
+fn fbm(noise: impl Fn(f32, f32) -> f32, x: f32, y: f32) -> f32 {
+    const OCTAVES: u32 = 6;
+    const LACUNARITY: f32 = 2.0;
+    const GAIN: f32 = 0.5;
+
+    let (mut sum, mut amplitude, mut frequency, mut total) = (0.0, 1.0, 1.0, 0.0);
+    for _ in 0..OCTAVES {
+        sum += noise(x * frequency, y * frequency) * amplitude;
+        total += amplitude;
+        amplitude *= GAIN;
+        frequency *= LACUNARITY;
+    }
+    sum / total
+}
+
+fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color::new(
+        channel(from.r, to.r),
+        channel(from.g, to.g),
+        channel(from.b, to.b),
+        channel(from.a, to.a),
+    )
+}
+
+fn gray(value: f32) -> Color {
+    let value = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Color::new(value, value, value, 255)
+}
+
+/// Procedural images, the `GenImage*` family of raylib
+///
+/// Every generator produces an 8 bit RGBA [Image]. Noise generators take a `seed`, the same
+/// arguments always give the same pixels.
+impl Image {
+    fn generate(width: i32, height: i32, mut pixel: impl FnMut(i32, i32) -> Color) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(pixel(x, y));
+            }
+        }
+        Self::from_colors(width, height, pixels).unwrap()
+    }
+
+    /// Plain `color`, same as [Image::new]
+    pub fn gen_color(width: i32, height: i32, color: Color) -> Self {
+        Self::new(width, height, color)
+    }
+
+    /// Gradient from `start` to `end` along `angle` degrees, `0` goes left to right and `90`
+    /// top to bottom
+    pub fn gen_gradient_linear(
+        width: i32,
+        height: i32,
+        angle: f32,
+        start: Color,
+        end: Color,
+    ) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let (half_w, half_h) = (width as f32 / 2.0, height as f32 / 2.0);
+        // Projection of the image corners on the direction, so both ends reach the edges
+        let extent = (half_w * cos).abs() + (half_h * sin).abs();
+        Self::generate(width, height, |x, y| {
+            let (dx, dy) = (x as f32 + 0.5 - half_w, y as f32 + 0.5 - half_h);
+            let t = 0.5 + (dx * cos + dy * sin) / (2.0 * extent.max(f32::EPSILON));
+            lerp_color(start, end, t)
+        })
+    }
+
+    /// Circular gradient from `inner` at the center to `outer`
+    ///
+    /// `density` is the fraction of the radius filled with `inner` before blending starts.
+    pub fn gen_gradient_radial(
+        width: i32,
+        height: i32,
+        density: f32,
+        inner: Color,
+        outer: Color,
+    ) -> Self {
+        let radius = width.min(height) as f32 / 2.0;
+        let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+        Self::generate(width, height, |x, y| {
+            let distance = (x as f32 + 0.5 - cx).hypot(y as f32 + 0.5 - cy) / radius.max(1.0);
+            lerp_color(inner, outer, blend(distance, density))
+        })
+    }
+
+    /// Square gradient from `inner` at the center to `outer` at the edges
+    pub fn gen_gradient_square(
+        width: i32,
+        height: i32,
+        density: f32,
+        inner: Color,
+        outer: Color,
+    ) -> Self {
+        let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+        Self::generate(width, height, |x, y| {
+            let distance = ((x as f32 + 0.5 - cx).abs() / cx.max(1.0))
+                .max((y as f32 + 0.5 - cy).abs() / cy.max(1.0));
+            lerp_color(inner, outer, blend(distance, density))
+        })
+    }
+
+    /// Checkerboard of `check_width` x `check_height` pixel squares, starting with `first` in the
+    /// top left corner
+    pub fn gen_checked(
+        width: i32,
+        height: i32,
+        check_width: i32,
+        check_height: i32,
+        first: Color,
+        second: Color,
+    ) -> Self {
+        let (check_width, check_height) = (check_width.max(1), check_height.max(1));
+        Self::generate(width, height, |x, y| {
+            if (x / check_width + y / check_height) % 2 == 0 {
+                first
+            } else {
+                second
+            }
+        })
+    }
+
+    /// Black and white pixels, `factor` is the chance of a pixel being white
+    pub fn gen_white_noise(width: i32, height: i32, factor: f32, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        Self::generate(width, height, |_, _| {
+            if rng.next_f32() < factor {
+                Color::WHITE
+            } else {
+                Color::BLACK
+            }
+        })
+    }
+
+    /// Grayscale fractal Perlin noise, `scale` is the size of the noise features per pixel
+    /// (higher zooms out) and `offset` scrolls through it
+    pub fn gen_perlin_noise(
+        width: i32,
+        height: i32,
+        offset_x: i32,
+        offset_y: i32,
+        scale: f32,
+        seed: u64,
+    ) -> Self {
+        let perm = Permutation::new(seed);
+        Self::gen_noise(width, height, offset_x, offset_y, scale, |x, y| {
+            perlin(&perm, x, y)
+        })
+    }
+
+    /// Grayscale fractal simplex noise, same parameters as [Image::gen_perlin_noise]
+    pub fn gen_simplex_noise(
+        width: i32,
+        height: i32,
+        offset_x: i32,
+        offset_y: i32,
+        scale: f32,
+        seed: u64,
+    ) -> Self {
+        let perm = Permutation::new(seed);
+        Self::gen_noise(width, height, offset_x, offset_y, scale, |x, y| {
+            simplex(&perm, x, y)
+        })
+    }
+
+    fn gen_noise(
+        width: i32,
+        height: i32,
+        offset_x: i32,
+        offset_y: i32,
+        scale: f32,
+        noise: impl Fn(f32, f32) -> f32,
+    ) -> Self {
+        // Same normalization as raylib, `scale` spans the smaller side
+        let side = width.min(height).max(1) as f32;
+        Self::generate(width, height, |x, y| {
+            let nx = (x + offset_x) as f32 * scale / side;
+            let ny = (y + offset_y) as f32 * scale / side;
+            gray((fbm(&noise, nx, ny) + 1.0) / 2.0)
+        })
+    }
+
+    /// Grayscale Worley noise, one random point per `tile_size` tile, pixels darken near the
+    /// closest point
+    pub fn gen_cellular(width: i32, height: i32, tile_size: i32, seed: u64) -> Self {
+        let tile_size = tile_size.max(1);
+        let (tiles_x, tiles_y) = (
+            (width.max(0) + tile_size - 1) / tile_size,
+            (height.max(0) + tile_size - 1) / tile_size,
+        );
+        let mut rng = Rng::new(seed);
+        let points: Vec<(f32, f32)> = (0..tiles_x * tiles_y)
+            .map(|index| {
+                let (tx, ty) = (index % tiles_x, index / tiles_x);
+                (
+                    (tx * tile_size) as f32 + rng.next_f32() * tile_size as f32,
+                    (ty * tile_size) as f32 + rng.next_f32() * tile_size as f32,
+                )
+            })
+            .collect();
+
+        Self::generate(width, height, |x, y| {
+            let (tile_x, tile_y) = (x / tile_size, y / tile_size);
+            let mut closest = f32::MAX;
+            for ty in (tile_y - 1).max(0)..=(tile_y + 1).min(tiles_y - 1) {
+                for tx in (tile_x - 1).max(0)..=(tile_x + 1).min(tiles_x - 1) {
+                    let (px, py) = points[(ty * tiles_x + tx) as usize];
+                    closest = closest.min((x as f32 - px).hypot(y as f32 - py));
+                }
+            }
+            gray(closest / tile_size as f32)
+        })
+    }
+}
+
+/// Blend factor for a normalized `distance` from the center, flat up to `density`
+fn blend(distance: f32, density: f32) -> f32 {
+    let density = density.clamp(0.0, 1.0);
+    if density >= 1.0 {
+        return 0.0;
+    }
+    (distance - density) / (1.0 - density)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradients_reach_both_colors() {
+        let image = Image::gen_gradient_linear(4, 2, 0.0, Color::BLACK, Color::WHITE);
+        let first = image.get_pixel(0, 0).unwrap();
+        let last = image.get_pixel(3, 1).unwrap();
+        assert!(first.r < 40 && last.r > 215);
+        assert_eq!(image.get_pixel(0, 0), image.get_pixel(0, 1));
+
+        let vertical = Image::gen_gradient_linear(2, 4, 90.0, Color::BLACK, Color::WHITE);
+        assert_eq!(vertical.get_pixel(0, 0), vertical.get_pixel(1, 0));
+        assert!(vertical.get_pixel(0, 0).unwrap().r < vertical.get_pixel(0, 3).unwrap().r);
+
+        let radial = Image::gen_gradient_radial(9, 9, 0.0, Color::WHITE, Color::BLACK);
+        assert!(radial.get_pixel(4, 4).unwrap().r > 200);
+        assert_eq!(radial.get_pixel(0, 0), Some(Color::BLACK));
+
+        let square = Image::gen_gradient_square(8, 8, 0.5, Color::WHITE, Color::BLACK);
+        assert_eq!(square.get_pixel(4, 4), Some(Color::WHITE));
+        assert_eq!(square.get_pixel(0, 4).unwrap().r, 64);
+    }
+
+    #[test]
+    fn checkerboard() {
+        let image = Image::gen_checked(4, 4, 2, 2, Color::RED, Color::BLUE);
+        assert_eq!(image.get_pixel(1, 1), Some(Color::RED));
+        assert_eq!(image.get_pixel(2, 0), Some(Color::BLUE));
+        assert_eq!(image.get_pixel(3, 3), Some(Color::RED));
+    }
+
+    #[test]
+    fn noise_is_deterministic() {
+        let generators: [fn(u64) -> Image; 4] = [
+            |seed| Image::gen_white_noise(16, 16, 0.5, seed),
+            |seed| Image::gen_perlin_noise(16, 16, 0, 0, 4.0, seed),
+            |seed| Image::gen_simplex_noise(16, 16, 0, 0, 4.0, seed),
+            |seed| Image::gen_cellular(16, 16, 4, seed),
+        ];
+        for generate in generators {
+            assert_eq!(generate(7), generate(7));
+            assert_ne!(generate(7), generate(8));
+        }
+    }
+
+    #[test]
+    fn white_noise_follows_factor() {
+        let image = Image::gen_white_noise(64, 64, 0.25, 1);
+        let white = image
+            .to_colors()
+            .iter()
+            .filter(|color| **color == Color::WHITE)
+            .count();
+        let ratio = white as f32 / (64.0 * 64.0);
+        assert!((ratio - 0.25).abs() < 0.05, "{ratio}");
+        assert!(
+            Image::gen_white_noise(8, 8, 0.0, 1)
+                .to_colors()
+                .iter()
+                .all(|color| *color == Color::BLACK)
+        );
+    }
+
+    #[test]
+    fn gradient_noise_is_smooth_and_in_range() {
+        let perm = Permutation::new(3);
+        for noise in [perlin, simplex] {
+            let mut previous = noise(&perm, 0.0, 0.5);
+            for step in 1..200 {
+                let value = noise(&perm, step as f32 * 0.01, 0.5);
+                assert!((-1.0..=1.0).contains(&value), "{value}");
+                assert!((value - previous).abs() < 0.1);
+                previous = value;
+            }
+        }
+        // Lattice points are always zero for Perlin noise
+        assert_eq!(perlin(&perm, 3.0, 5.0), 0.0);
+
+        let scrolled = Image::gen_perlin_noise(8, 8, 4, 0, 4.0, 3);
+        let base = Image::gen_perlin_noise(12, 8, 0, 0, 4.0, 3);
+        assert_eq!(scrolled.get_pixel(0, 0), base.get_pixel(4, 0));
+    }
+
+    #[test]
+    fn cellular_is_dark_near_points() {
+        let image = Image::gen_cellular(32, 32, 8, 5);
+        let darkest = image.to_colors().iter().map(|color| color.r).min().unwrap();
+        assert!(darkest < 40);
+        assert!(image.to_colors().iter().all(|color| color.a == 255));
+    }
+}
@@ -0,0 +1,446 @@
+use std::path::Path;
+
+use rustyray_sys::{
+    consts::{PixelFormat, TextureFilter, TextureWrap},
+    ffi::{
+        self, gen_texture_mipmaps, is_window_ready, load_texture_from_image, set_texture_filter,
+        set_texture_wrap, update_texture, update_texture_rec,
+    },
+    texture::{
+        RenderTexture as RayRenderTexture, RenderTextureLoadError, Texture as RayTexture,
+        TextureLoadError,
+    },
+};
+
+use thiserror::Error;
+
+use super::assets::{Asset, AssetLoader, LoadContext, SyncAsset};
+use super::color::Color;
+use super::math::{Rectangle, Vector2i};
+
+mod atlas;
+mod cpu;
+mod export;
+mod generate;
+
+pub use atlas::*;
+pub use cpu::*;
+pub use export::*;
+
+#[derive(Debug, PartialEq)]
+pub struct Texture {
+    inner: RayTexture,
+}
+
+#[derive(Debug)]
+pub struct RenderTexture {
+    inner: RayRenderTexture,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum TextureUpdateError {
+    #[error("texture format {0:?} can not be updated from 8 bit RGBA colors")]
+    FormatMismatch(PixelFormat),
+    #[error("expected {expected} pixels but got {actual}")]
+    SizeMismatch { expected: usize, actual: usize },
+    #[error("region {0:?} is not pixel aligned or not inside the texture")]
+    OutOfBounds(Rectangle),
+}
+
+/// Sampling settings applied to a [Texture] right after it is uploaded
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureOptions {
+    /// `None` keeps raylib's default, [TextureFilter::Point]
+    pub filter: Option<TextureFilter>,
+    /// `None` keeps raylib's default, [TextureWrap::Repeat]
+    pub wrap: Option<TextureWrap>,
+    pub mipmaps: bool,
+}
+
+impl TextureOptions {
+    pub fn filter(mut self, filter: TextureFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn wrap(mut self, wrap: TextureWrap) -> Self {
+        self.wrap = Some(wrap);
+        self
+    }
+
+    pub fn mipmaps(mut self) -> Self {
+        self.mipmaps = true;
+        self
+    }
+
+    fn apply(&self, texture: &mut RayTexture) {
+        unsafe {
+            if self.mipmaps {
+                gen_texture_mipmaps(texture);
+            }
+            if let Some(filter) = self.filter {
+                set_texture_filter(texture.clone(), filter);
+            }
+            if let Some(wrap) = self.wrap {
+                set_texture_wrap(texture.clone(), wrap);
+            }
+        }
+    }
+}
+
+/// Key used to load a [Texture], the same file loaded with different options gives separate textures
+///
+/// Plain paths convert into a key with default options:
+/// ```no_run
+/// # use rustyray::prelude::*;
+/// # fn load(assets: &mut AssetManager) {
+/// let smooth: Handle<Texture> = assets.load("assets/background.png");
+/// let pixel_art: Handle<Texture> = assets.load(
+///     TextureKey::new("assets/player.png")
+///         .options(TextureOptions::default().filter(TextureFilter::Point)),
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextureKey {
+    pub path: String,
+    pub options: TextureOptions,
+}
+
+impl TextureKey {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            options: TextureOptions::default(),
+        }
+    }
+
+    pub fn options(mut self, options: TextureOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl From<String> for TextureKey {
+    fn from(path: String) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<&str> for TextureKey {
+    fn from(path: &str) -> Self {
+        Self::new(path)
+    }
+}
+
+impl Texture {
+    /// Upload `image` to the GPU
+    pub fn from_image(image: &Image) -> Result<Self, TextureLoadError> {
+        if !unsafe { is_window_ready() } {
+            return Err(TextureLoadError::WindowNotReady());
+        }
+
+        Ok(Self {
+            inner: unsafe { load_texture_from_image(image.as_ray()) },
+        })
+    }
+
+    /// Allocate a texture with undefined contents, fill it with [Texture::update]
+    pub fn empty(width: i32, height: i32, format: PixelFormat) -> Result<Self, TextureLoadError> {
+        Ok(Self {
+            inner: RayTexture::empty(width, height, format)?,
+        })
+    }
+
+    pub fn size(&self) -> Vector2i {
+        Vector2i {
+            x: self.inner.width,
+            y: self.inner.height,
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.inner.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.inner.height
+    }
+
+    /// Set the scaling filter, [TextureFilter::Trilinear] and up need [Texture::gen_mipmaps]
+    pub fn set_filter(&self, filter: TextureFilter) {
+        unsafe { set_texture_filter(self.inner.clone(), filter) }
+    }
+
+    pub fn set_wrap(&self, wrap: TextureWrap) {
+        unsafe { set_texture_wrap(self.inner.clone(), wrap) }
+    }
+
+    /// Generate the GPU mipmap chain
+    pub fn gen_mipmaps(&mut self) {
+        unsafe { gen_texture_mipmaps(&mut self.inner) }
+    }
+
+    pub fn mipmaps(&self) -> i32 {
+        self.inner.mipmaps
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        self.inner
+            .pixel_format()
+            .expect("raylib textures have a valid format")
+    }
+
+    /// Replace every pixel, `pixels` is row by row from the top and must cover the whole texture
+    pub fn update(&mut self, pixels: &[Color]) -> Result<(), TextureUpdateError> {
+        let region = Rectangle::new(0.0, 0.0, self.width() as f32, self.height() as f32);
+        self.check_update(region, pixels)?;
+        unsafe { update_texture(self.inner.clone(), pixels.as_ptr().cast()) };
+        Ok(())
+    }
+
+    /// Replace the pixels inside `region`, which must be whole pixels inside the texture
+    pub fn update_region(
+        &mut self,
+        region: Rectangle,
+        pixels: &[Color],
+    ) -> Result<(), TextureUpdateError> {
+        self.check_update(region, pixels)?;
+        unsafe { update_texture_rec(self.inner.clone(), region.into(), pixels.as_ptr().cast()) };
+        Ok(())
+    }
+
+    fn check_update(&self, region: Rectangle, pixels: &[Color]) -> Result<(), TextureUpdateError> {
+        let format = self.format();
+        if format != PixelFormat::UncompressedR8G8B8A8 {
+            return Err(TextureUpdateError::FormatMismatch(format));
+        }
+        check_region(self.size(), region, pixels.len())
+    }
+
+    pub(crate) fn as_ray(&self) -> RayTexture {
+        self.inner.clone()
+    }
+}
+
+impl RenderTexture {
+    pub(crate) fn from_key(key: RenderTextureKey) -> Result<Self, RenderTextureLoadError> {
+        let RenderTextureKey {
+            width,
+            height,
+            format,
+        } = key;
+        let inner = match format {
+            PixelFormat::UncompressedR8G8B8A8 => RayRenderTexture::new(width, height)?,
+            format => RayRenderTexture::with_format(width, height, format)?,
+        };
+        Ok(Self { inner })
+    }
+
+    /// Pixel format of the color buffer
+    pub fn format(&self) -> PixelFormat {
+        self.inner
+            .texture
+            .pixel_format()
+            .expect("raylib textures have a valid format")
+    }
+
+    pub fn size(&self) -> Vector2i {
+        Vector2i {
+            x: self.inner.texture.width,
+            y: self.inner.texture.height,
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.inner.texture.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.inner.texture.height
+    }
+
+    pub fn set_filter(&self, filter: TextureFilter) {
+        unsafe { set_texture_filter(self.inner.texture.clone(), filter) }
+    }
+
+    pub fn set_wrap(&self, wrap: TextureWrap) {
+        unsafe { set_texture_wrap(self.inner.texture.clone(), wrap) }
+    }
+
+    /// Generate the mipmap chain from the current contents, call again after drawing to it
+    pub fn gen_mipmaps(&mut self) {
+        unsafe { gen_texture_mipmaps(&mut self.inner.texture) }
+    }
+
+    pub(crate) fn as_ray(&self) -> RayRenderTexture {
+        self.inner.clone()
+    }
+}
+
+impl Drop for Texture {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::unload_texture(self.inner.clone());
+        }
+    }
+}
+
+impl Drop for RenderTexture {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::unload_render_texture(self.inner.clone());
+        }
+    }
+}
+
+/// Validate an update of `len` pixels to `region` of a texture with `size`
+fn check_region(size: Vector2i, region: Rectangle, len: usize) -> Result<(), TextureUpdateError> {
+    let Rectangle {
+        x,
+        y,
+        width,
+        height,
+    } = region;
+    let aligned = [x, y, width, height].iter().all(|v| v.fract() == 0.0);
+    if !aligned
+        || x < 0.0
+        || y < 0.0
+        || width <= 0.0
+        || height <= 0.0
+        || x + width > size.x as f32
+        || y + height > size.y as f32
+    {
+        return Err(TextureUpdateError::OutOfBounds(region));
+    }
+
+    let expected = width as usize * height as usize;
+    if len != expected {
+        return Err(TextureUpdateError::SizeMismatch {
+            expected,
+            actual: len,
+        });
+    }
+    Ok(())
+}
+
+/// Bytes used by every mipmap level of `texture`
+pub(crate) fn texture_size(texture: &RayTexture) -> usize {
+    texture.pixel_format().map_or(0, |format| {
+        format.mipmaps_data_size(texture.width, texture.height, texture.mipmaps)
+    })
+}
+
+impl Asset for Texture {
+    fn size_estimate(&self) -> usize {
+        texture_size(&self.inner)
+    }
+}
+
+impl AssetLoader for Texture {
+    type Key = TextureKey;
+    type Error = TextureLoadError;
+    type Decoded = (Image, TextureOptions);
+
+    async fn decode(key: Self::Key, ctx: LoadContext) -> Result<Self::Decoded, Self::Error> {
+        let image = <Image as AssetLoader>::decode(key.path, ctx)
+            .await
+            .map_err(|err| match err {
+                ImageError::FileNotFound(path) => TextureLoadError::FileNotFound(path),
+                _ => TextureLoadError::FileNotFound(String::from("failed to decode image")),
+            })?;
+        Ok((image, key.options))
+    }
+
+    fn finish((image, options): Self::Decoded) -> Result<Self, Self::Error> {
+        let mut texture = Self::from_image(&image)?;
+        options.apply(&mut texture.inner);
+        Ok(texture)
+    }
+
+    fn path(key: &Self::Key) -> Option<&Path> {
+        Some(Path::new(&key.path))
+    }
+}
+
+impl Asset for RenderTexture {
+    fn size_estimate(&self) -> usize {
+        // The depth attachment's format field is a placeholder, count it as a 32 bit buffer
+        let depth = &self.inner.depth;
+        let depth_size = if depth.id == 0 {
+            0
+        } else {
+            depth.width.max(0) as usize * depth.height.max(0) as usize * 4
+        };
+        texture_size(&self.inner.texture) + depth_size
+    }
+}
+
+/// Size and color format of a [RenderTexture], `(width, height)` uses 8 bit RGBA
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderTextureKey {
+    pub width: i32,
+    pub height: i32,
+    pub format: PixelFormat,
+}
+
+impl From<(i32, i32)> for RenderTextureKey {
+    fn from((width, height): (i32, i32)) -> Self {
+        Self::from((width, height, PixelFormat::UncompressedR8G8B8A8))
+    }
+}
+
+impl From<(i32, i32, PixelFormat)> for RenderTextureKey {
+    fn from((width, height, format): (i32, i32, PixelFormat)) -> Self {
+        Self {
+            width,
+            height,
+            format,
+        }
+    }
+}
+
+impl SyncAsset for RenderTexture {
+    type Key = RenderTextureKey;
+    type Error = RenderTextureLoadError;
+
+    fn create(key: Self::Key) -> Result<Self, Self::Error> {
+        Self::from_key(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_regions_are_validated() {
+        let size = Vector2i { x: 8, y: 4 };
+        assert_eq!(
+            check_region(size, Rectangle::new(0.0, 0.0, 8.0, 4.0), 32),
+            Ok(())
+        );
+        assert_eq!(
+            check_region(size, Rectangle::new(6.0, 2.0, 2.0, 2.0), 4),
+            Ok(())
+        );
+        assert_eq!(
+            check_region(size, Rectangle::new(0.0, 0.0, 8.0, 4.0), 31),
+            Err(TextureUpdateError::SizeMismatch {
+                expected: 32,
+                actual: 31
+            })
+        );
+        for region in [
+            Rectangle::new(7.0, 0.0, 2.0, 1.0),
+            Rectangle::new(-1.0, 0.0, 1.0, 1.0),
+            Rectangle::new(0.5, 0.0, 1.0, 1.0),
+            Rectangle::new(0.0, 0.0, 0.0, 1.0),
+        ] {
+            assert_eq!(
+                check_region(size, region, 1),
+                Err(TextureUpdateError::OutOfBounds(region))
+            );
+        }
+    }
+}
@@ -1,7 +1,9 @@
 mod easings;
 mod rectangle;
+mod spline;
 mod vector;
 
 pub use easings::*;
 pub use rectangle::*;
+pub use spline::*;
 pub use vector::*;
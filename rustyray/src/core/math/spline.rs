@@ -0,0 +1,372 @@
+use super::Vector2;
+
+/// Point on the segment from `start` to `end`
+pub fn spline_point_linear(start: Vector2, end: Vector2, t: f32) -> Vector2 {
+    start * (1.0 - t) + end * t
+}
+
+/// Point on a uniform cubic B-spline segment, it passes near but not through `p2` and `p3`
+pub fn spline_point_basis(p1: Vector2, p2: Vector2, p3: Vector2, p4: Vector2, t: f32) -> Vector2 {
+    let (t2, t3) = (t * t, t * t * t);
+    let u = 1.0 - t;
+    (p1 * (u * u * u)
+        + p2 * (3.0 * t3 - 6.0 * t2 + 4.0)
+        + p3 * (-3.0 * t3 + 3.0 * t2 + 3.0 * t + 1.0)
+        + p4 * t3)
+        * (1.0 / 6.0)
+}
+
+/// Point on a Catmull-Rom segment running from `p2` to `p3`
+pub fn spline_point_catmull_rom(
+    p1: Vector2,
+    p2: Vector2,
+    p3: Vector2,
+    p4: Vector2,
+    t: f32,
+) -> Vector2 {
+    let (t2, t3) = (t * t, t * t * t);
+    (p2 * 2.0
+        + (p3 - p1) * t
+        + (p1 * 2.0 - p2 * 5.0 + p3 * 4.0 - p4) * t2
+        + (p2 * 3.0 - p1 - p3 * 3.0 + p4) * t3)
+        * 0.5
+}
+
+/// Point on a quadratic Bezier curve from `start` to `end`
+pub fn spline_point_bezier_quad(start: Vector2, control: Vector2, end: Vector2, t: f32) -> Vector2 {
+    let u = 1.0 - t;
+    start * (u * u) + control * (2.0 * u * t) + end * (t * t)
+}
+
+/// Point on a cubic Bezier curve from `start` to `end`
+pub fn spline_point_bezier_cubic(
+    start: Vector2,
+    control1: Vector2,
+    control2: Vector2,
+    end: Vector2,
+    t: f32,
+) -> Vector2 {
+    let u = 1.0 - t;
+    start * (u * u * u)
+        + control1 * (3.0 * u * u * t)
+        + control2 * (3.0 * u * t * t)
+        + end * (t * t * t)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SplineKind {
+    /// Straight lines through every point
+    Linear,
+    /// Smooth curve through every point except the first and last, which only steer it
+    CatmullRom,
+    /// Uniform cubic B-spline, smooth and approximating: it doesn't pass through the points
+    Basis,
+    /// `start, control, end, control, end, ...`
+    QuadraticBezier,
+    /// `start, control, control, end, control, control, end, ...`
+    CubicBezier,
+}
+
+/// A curve made of several segments, evaluated with a `t` from 0.0 to 1.0 over its whole length
+///
+/// `t` is spread evenly across segments, not across distance: use [ArcLengthTable] to move at a
+/// constant speed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spline {
+    pub kind: SplineKind,
+    pub points: Vec<Vector2>,
+}
+
+impl Spline {
+    pub fn new(kind: SplineKind, points: impl Into<Vec<Vector2>>) -> Self {
+        Self {
+            kind,
+            points: points.into(),
+        }
+    }
+
+    pub fn linear(points: impl Into<Vec<Vector2>>) -> Self {
+        Self::new(SplineKind::Linear, points)
+    }
+
+    pub fn catmull_rom(points: impl Into<Vec<Vector2>>) -> Self {
+        Self::new(SplineKind::CatmullRom, points)
+    }
+
+    pub fn basis(points: impl Into<Vec<Vector2>>) -> Self {
+        Self::new(SplineKind::Basis, points)
+    }
+
+    pub fn quadratic_bezier(points: impl Into<Vec<Vector2>>) -> Self {
+        Self::new(SplineKind::QuadraticBezier, points)
+    }
+
+    pub fn cubic_bezier(points: impl Into<Vec<Vector2>>) -> Self {
+        Self::new(SplineKind::CubicBezier, points)
+    }
+
+    /// Number of curve pieces, 0 when there are too few points
+    pub fn segment_count(&self) -> usize {
+        let len = self.points.len();
+        match self.kind {
+            SplineKind::Linear => len.saturating_sub(1),
+            SplineKind::CatmullRom | SplineKind::Basis => len.saturating_sub(3),
+            SplineKind::QuadraticBezier => len.saturating_sub(1) / 2,
+            SplineKind::CubicBezier => len.saturating_sub(1) / 3,
+        }
+    }
+
+    /// Segment index and the `t` inside it for a spline wide `t`
+    fn locate(&self, t: f32) -> Option<(usize, f32)> {
+        let count = self.segment_count();
+        if count == 0 {
+            return None;
+        }
+        let scaled = t.clamp(0.0, 1.0) * count as f32;
+        let segment = (scaled as usize).min(count - 1);
+        Some((segment, scaled - segment as f32))
+    }
+
+    /// Control points of `segment`
+    fn segment(&self, segment: usize) -> &[Vector2] {
+        let p = &self.points;
+        match self.kind {
+            SplineKind::Linear => &p[segment..segment + 2],
+            SplineKind::CatmullRom | SplineKind::Basis => &p[segment..segment + 4],
+            SplineKind::QuadraticBezier => &p[segment * 2..segment * 2 + 3],
+            SplineKind::CubicBezier => &p[segment * 3..segment * 3 + 4],
+        }
+    }
+
+    /// Position at `t`, the first point (or zero) when the spline has no segments
+    pub fn point(&self, t: f32) -> Vector2 {
+        let Some((segment, t)) = self.locate(t) else {
+            return self.points.first().copied().unwrap_or(Vector2::ZERO);
+        };
+        match (self.kind, self.segment(segment)) {
+            (SplineKind::Linear, &[a, b]) => spline_point_linear(a, b, t),
+            (SplineKind::CatmullRom, &[a, b, c, d]) => spline_point_catmull_rom(a, b, c, d, t),
+            (SplineKind::Basis, &[a, b, c, d]) => spline_point_basis(a, b, c, d, t),
+            (SplineKind::QuadraticBezier, &[a, b, c]) => spline_point_bezier_quad(a, b, c, t),
+            (SplineKind::CubicBezier, &[a, b, c, d]) => spline_point_bezier_cubic(a, b, c, d, t),
+            _ => unreachable!("segment() returns the point count of its kind"),
+        }
+    }
+
+    /// Derivative of the position with respect to `t`
+    pub fn derivative(&self, t: f32) -> Vector2 {
+        let Some((segment, t)) = self.locate(t) else {
+            return Vector2::ZERO;
+        };
+        let (t2, u) = (t * t, 1.0 - t);
+        let local = match (self.kind, self.segment(segment)) {
+            (SplineKind::Linear, &[a, b]) => b - a,
+            (SplineKind::CatmullRom, &[a, b, c, d]) => {
+                ((c - a)
+                    + (a * 2.0 - b * 5.0 + c * 4.0 - d) * (2.0 * t)
+                    + (b * 3.0 - a - c * 3.0 + d) * (3.0 * t2))
+                    * 0.5
+            }
+            (SplineKind::Basis, &[a, b, c, d]) => {
+                (a * (-3.0 * u * u)
+                    + b * (9.0 * t2 - 12.0 * t)
+                    + c * (-9.0 * t2 + 6.0 * t + 3.0)
+                    + d * (3.0 * t2))
+                    * (1.0 / 6.0)
+            }
+            (SplineKind::QuadraticBezier, &[a, b, c]) => (b - a) * (2.0 * u) + (c - b) * (2.0 * t),
+            (SplineKind::CubicBezier, &[a, b, c, d]) => {
+                (b - a) * (3.0 * u * u) + (c - b) * (6.0 * u * t) + (d - c) * (3.0 * t2)
+            }
+            _ => unreachable!("segment() returns the point count of its kind"),
+        };
+        // Each segment only covers 1 / count of the spline wide t
+        local * self.segment_count() as f32
+    }
+
+    /// Unit direction of travel at `t`, zero where the curve stops
+    pub fn tangent(&self, t: f32) -> Vector2 {
+        self.derivative(t).normalized()
+    }
+
+    /// Unit vector 90 degrees counter-clockwise from [Spline::tangent], in y-down screen space
+    pub fn normal(&self, t: f32) -> Vector2 {
+        let tangent = self.tangent(t);
+        Vector2::new(tangent.y, -tangent.x)
+    }
+
+    /// `samples + 1` evenly spaced points in `t`, first and last included
+    pub fn sample(&self, samples: usize) -> Vec<Vector2> {
+        let samples = samples.max(1);
+        (0..=samples)
+            .map(|i| self.point(i as f32 / samples as f32))
+            .collect()
+    }
+
+    /// Approximate length using `samples_per_segment` straight pieces per segment
+    pub fn length(&self, samples_per_segment: usize) -> f32 {
+        self.arc_length_table(samples_per_segment).length()
+    }
+
+    pub fn arc_length_table(&self, samples_per_segment: usize) -> ArcLengthTable {
+        let samples = (self.segment_count() * samples_per_segment.max(1)).max(1);
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut total = 0.0;
+        let mut previous = self.point(0.0);
+        lengths.push(0.0);
+        for i in 1..=samples {
+            let point = self.point(i as f32 / samples as f32);
+            total += (point - previous).length();
+            lengths.push(total);
+            previous = point;
+        }
+        ArcLengthTable { lengths }
+    }
+
+    /// Position `distance` units along the curve
+    pub fn point_at_distance(&self, table: &ArcLengthTable, distance: f32) -> Vector2 {
+        self.point(table.t_at_distance(distance))
+    }
+}
+
+/// Cumulative lengths of a [Spline] sampled at even `t` steps, maps distances back to `t`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLengthTable {
+    lengths: Vec<f32>,
+}
+
+impl ArcLengthTable {
+    pub fn length(&self) -> f32 {
+        self.lengths.last().copied().unwrap_or(0.0)
+    }
+
+    /// The `t` at which the curve is `distance` long, clamped to the ends
+    pub fn t_at_distance(&self, distance: f32) -> f32 {
+        let steps = self.lengths.len() - 1;
+        if steps == 0 || self.length() <= 0.0 {
+            return 0.0;
+        }
+        let distance = distance.clamp(0.0, self.length());
+        let upper = self
+            .lengths
+            .partition_point(|&length| length < distance)
+            .clamp(1, steps);
+        let (start, end) = (self.lengths[upper - 1], self.lengths[upper]);
+        let fraction = if end > start {
+            (distance - start) / (end - start)
+        } else {
+            0.0
+        };
+        (upper as f32 - 1.0 + fraction) / steps as f32
+    }
+
+    /// `t` for a fraction 0.0 to 1.0 of the total length
+    pub fn t_at_fraction(&self, fraction: f32) -> f32 {
+        self.t_at_distance(fraction * self.length())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Vector2, b: Vector2) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    #[test]
+    fn curves_hit_their_end_points() {
+        let points = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 20.0),
+            Vector2::new(30.0, 20.0),
+            Vector2::new(40.0, 0.0),
+            Vector2::new(50.0, -10.0),
+        ];
+
+        let cubic = Spline::cubic_bezier(&points[..4]);
+        assert!(close(cubic.point(0.0), points[0]));
+        assert!(close(cubic.point(1.0), points[3]));
+
+        let quad = Spline::quadratic_bezier(points);
+        assert_eq!(quad.segment_count(), 2);
+        assert!(close(quad.point(0.5), points[2]));
+        assert!(close(quad.point(1.0), points[4]));
+
+        // Catmull-Rom runs through the inner points
+        let catmull = Spline::catmull_rom(points);
+        assert_eq!(catmull.segment_count(), 2);
+        assert!(close(catmull.point(0.0), points[1]));
+        assert!(close(catmull.point(0.5), points[2]));
+        assert!(close(catmull.point(1.0), points[3]));
+
+        let linear = Spline::linear(points);
+        assert!(close(linear.point(0.125), Vector2::new(5.0, 10.0)));
+        assert!(close(Spline::linear(vec![]).point(0.5), Vector2::ZERO));
+    }
+
+    #[test]
+    fn basis_of_a_straight_line_stays_on_it() {
+        let line: Vec<_> = (0..6).map(|i| Vector2::new(i as f32 * 2.0, 0.0)).collect();
+        let spline = Spline::basis(line);
+        assert!(close(spline.point(0.0), Vector2::new(2.0, 0.0)));
+        assert!(close(spline.point(1.0), Vector2::new(8.0, 0.0)));
+        assert!(close(spline.tangent(0.3), Vector2::new(1.0, 0.0)));
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences() {
+        let points = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(5.0, 30.0),
+            Vector2::new(25.0, -10.0),
+            Vector2::new(40.0, 5.0),
+            Vector2::new(60.0, 20.0),
+            Vector2::new(70.0, 0.0),
+            Vector2::new(90.0, 10.0),
+        ];
+        let kinds = [
+            SplineKind::Linear,
+            SplineKind::CatmullRom,
+            SplineKind::Basis,
+            SplineKind::QuadraticBezier,
+            SplineKind::CubicBezier,
+        ];
+        for kind in kinds {
+            let spline = Spline::new(kind, points);
+            for t in [0.1, 0.4, 0.77] {
+                let h = 1e-3;
+                let numeric = (spline.point(t + h) - spline.point(t - h)) * (0.5 / h);
+                let analytic = spline.derivative(t);
+                assert!(
+                    (numeric - analytic).length() < analytic.length() * 1e-2 + 1e-2,
+                    "{kind:?} at {t}: {numeric} vs {analytic}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn arc_length_parameterization() {
+        let line = Spline::linear([Vector2::new(0.0, 0.0), Vector2::new(30.0, 40.0)]);
+        assert!((line.length(4) - 50.0).abs() < 1e-4);
+
+        // Quarter circle of radius 100 approximated by a cubic bezier
+        let k = 0.552_284_8 * 100.0;
+        let arc = Spline::cubic_bezier([
+            Vector2::new(100.0, 0.0),
+            Vector2::new(100.0, k),
+            Vector2::new(k, 100.0),
+            Vector2::new(0.0, 100.0),
+        ]);
+        let table = arc.arc_length_table(64);
+        let quarter = std::f32::consts::FRAC_PI_2 * 100.0;
+        assert!((table.length() - quarter).abs() < quarter * 1e-3);
+
+        // Equal distances land on equal angles
+        let halfway = arc.point_at_distance(&table, table.length() / 2.0);
+        assert!((halfway.x - halfway.y).abs() < 0.05);
+        assert_eq!(table.t_at_distance(-5.0), 0.0);
+        assert_eq!(table.t_at_fraction(2.0), 1.0);
+    }
+}
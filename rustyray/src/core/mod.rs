@@ -5,4 +5,9 @@ pub mod consts;
 pub mod drawing;
 pub mod image;
 pub mod math;
+pub mod pak;
+pub mod sprite;
+pub mod text;
+pub mod vfs;
 pub mod window;
+mod worker;
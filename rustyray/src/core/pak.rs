@@ -0,0 +1,378 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+
+use futures_lite::{AsyncReadExt, AsyncSeekExt};
+use thiserror::Error;
+
+const MAGIC: &[u8; 5] = b"RRPAK";
+const VERSION: u8 = 1;
+/// Magic, version and entry count
+const HEADER_SIZE: u64 = MAGIC.len() as u64 + 1 + 4;
+/// Path length, an empty path and the offset and size
+const MIN_ENTRY_SIZE: u64 = 2 + 8 + 8;
+
+#[derive(Debug, Error)]
+pub enum PakError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("not a pak archive")]
+    InvalidMagic,
+    #[error("unsupported pak version {0}")]
+    UnsupportedVersion(u8),
+    #[error("corrupted pak archive: {0}")]
+    Corrupted(String),
+    #[error("invalid entry path: {0}")]
+    InvalidPath(String),
+    #[error("entry not found in pak archive: {0}")]
+    NotFound(String),
+}
+
+impl From<PakError> for io::Error {
+    fn from(value: PakError) -> Self {
+        match value {
+            PakError::Io(err) => err,
+            PakError::NotFound(_) => io::Error::new(io::ErrorKind::NotFound, value),
+            _ => io::Error::new(io::ErrorKind::InvalidData, value),
+        }
+    }
+}
+
+/// Turn a path into the name it is stored under, `./assets\a.png` becomes `assets/a.png`
+pub(crate) fn entry_name(path: &Path) -> Result<String, PakError> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(
+                part.to_str()
+                    .ok_or_else(|| PakError::InvalidPath(path.display().to_string()))?
+                    .replace('\\', "/"),
+            ),
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+            Component::ParentDir => return Err(PakError::InvalidPath(path.display().to_string())),
+        }
+    }
+    if parts.is_empty() {
+        return Err(PakError::InvalidPath(path.display().to_string()));
+    }
+    Ok(parts.join("/"))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PakEntry {
+    offset: u64,
+    size: u64,
+}
+
+/// A read-only archive of files packed with [PakBuilder]
+///
+/// Only the index is kept in memory, entries are read from disk on demand.
+///
+/// Layout, all integers little endian:
+///
+/// ```text
+/// magic "RRPAK" | version u8 | entry count u32
+/// entries: path length u16 | utf-8 path | data offset u64 | data size u64
+/// data blobs, offsets are from the start of the file
+/// ```
+#[derive(Debug)]
+pub struct Pak {
+    path: PathBuf,
+    entries: HashMap<String, PakEntry>,
+}
+
+fn read_u16(reader: &mut impl Read) -> io::Result<u16> {
+    let mut buf = [0; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut buf = [0; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+impl Pak {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PakError> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+
+        let mut magic = [0; 5];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(PakError::InvalidMagic);
+        }
+        let mut version = [0; 1];
+        reader.read_exact(&mut version)?;
+        if version[0] != VERSION {
+            return Err(PakError::UnsupportedVersion(version[0]));
+        }
+
+        let count = read_u32(&mut reader)?;
+        // Don't trust the header with the allocation size
+        let max_count = len.saturating_sub(HEADER_SIZE) / MIN_ENTRY_SIZE;
+        if u64::from(count) > max_count {
+            return Err(PakError::Corrupted(format!(
+                "{count} entries can not fit in {len} bytes"
+            )));
+        }
+        let mut entries = HashMap::with_capacity(count as usize);
+        for _ in 0..count {
+            let name_len = read_u16(&mut reader)?;
+            let mut name = vec![0; name_len as usize];
+            reader.read_exact(&mut name)?;
+            let name = String::from_utf8(name)
+                .map_err(|_| PakError::Corrupted(String::from("entry name is not utf-8")))?;
+            let entry = PakEntry {
+                offset: read_u64(&mut reader)?,
+                size: read_u64(&mut reader)?,
+            };
+            if entry
+                .offset
+                .checked_add(entry.size)
+                .is_none_or(|end| end > len)
+            {
+                return Err(PakError::Corrupted(format!(
+                    "entry {name} is out of bounds"
+                )));
+            }
+            entries.insert(name, entry);
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        entry_name(path.as_ref()).is_ok_and(|name| self.entries.contains_key(&name))
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    pub async fn read(&self, path: impl AsRef<Path>) -> Result<Vec<u8>, PakError> {
+        let name = entry_name(path.as_ref())?;
+        let entry = *self
+            .entries
+            .get(&name)
+            .ok_or_else(|| PakError::NotFound(name.clone()))?;
+
+        let mut file = async_fs::File::open(&self.path).await?;
+        file.seek(SeekFrom::Start(entry.offset)).await?;
+        let mut bytes = vec![0; entry.size as usize];
+        file.read_exact(&mut bytes).await?;
+        Ok(bytes)
+    }
+}
+
+#[derive(Debug)]
+enum PakInput {
+    File(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+/// Packs files into a [Pak] archive
+///
+/// # Examples
+/// ```no_run
+/// use rustyray::prelude::*;
+///
+/// PakBuilder::new()
+///     .add_dir("assets")
+///     .unwrap()
+///     .write("assets.pak")
+///     .unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct PakBuilder {
+    entries: BTreeMap<String, PakInput>,
+}
+
+impl PakBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file stored under `name`
+    pub fn add_file(
+        mut self,
+        name: impl AsRef<Path>,
+        path: impl Into<PathBuf>,
+    ) -> Result<Self, PakError> {
+        self.entries
+            .insert(entry_name(name.as_ref())?, PakInput::File(path.into()));
+        Ok(self)
+    }
+
+    /// Add in-memory data stored under `name`
+    pub fn add_bytes(mut self, name: impl AsRef<Path>, bytes: Vec<u8>) -> Result<Self, PakError> {
+        self.entries
+            .insert(entry_name(name.as_ref())?, PakInput::Bytes(bytes));
+        Ok(self)
+    }
+
+    /// Recursively add every file in `dir`, stored under the same relative path used to load it from disk
+    pub fn add_dir(mut self, dir: impl AsRef<Path>) -> Result<Self, PakError> {
+        let mut stack = vec![dir.as_ref().to_path_buf()];
+        while let Some(dir) = stack.pop() {
+            for entry in std::fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    stack.push(path);
+                } else {
+                    self = self.add_file(&path, path.clone())?;
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), PakError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<(), PakError> {
+        let sizes = self
+            .entries
+            .values()
+            .map(|input| match input {
+                PakInput::File(path) => Ok(std::fs::metadata(path)?.len()),
+                PakInput::Bytes(bytes) => Ok(bytes.len() as u64),
+            })
+            .collect::<Result<Vec<_>, PakError>>()?;
+
+        let count = u32::try_from(self.entries.len())
+            .map_err(|_| PakError::Corrupted(String::from("too many entries")))?;
+        let index_len: u64 = self
+            .entries
+            .keys()
+            .map(|name| 2 + name.len() as u64 + 16)
+            .sum();
+        let mut offset = (MAGIC.len() + 1 + 4) as u64 + index_len;
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&count.to_le_bytes())?;
+        for (name, size) in self.entries.keys().zip(&sizes) {
+            let name_len =
+                u16::try_from(name.len()).map_err(|_| PakError::InvalidPath(name.clone()))?;
+            writer.write_all(&name_len.to_le_bytes())?;
+            writer.write_all(name.as_bytes())?;
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&size.to_le_bytes())?;
+            offset += size;
+        }
+
+        for (input, size) in self.entries.values().zip(sizes) {
+            match input {
+                PakInput::File(path) => {
+                    let copied = io::copy(&mut File::open(path)?.take(size), writer)?;
+                    if copied != size {
+                        return Err(PakError::Io(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            format!("{} changed while packing", path.display()),
+                        )));
+                    }
+                }
+                PakInput::Bytes(bytes) => writer.write_all(bytes)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("rustyray-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn entry_names_are_normalized() {
+        assert_eq!(
+            entry_name(Path::new("./assets/wabbit_alpha.png")).unwrap(),
+            "assets/wabbit_alpha.png"
+        );
+        assert!(entry_name(Path::new("../secret")).is_err());
+        assert!(entry_name(Path::new("")).is_err());
+    }
+
+    #[test]
+    fn pak_roundtrip() {
+        let dir = temp_path("pak-dir");
+        std::fs::create_dir_all(dir.join("audio")).unwrap();
+        std::fs::write(dir.join("a.txt"), b"first").unwrap();
+        std::fs::write(dir.join("audio/b.txt"), b"second").unwrap();
+
+        let pak_path = temp_path("roundtrip.pak");
+        PakBuilder::new()
+            .add_dir(&dir)
+            .unwrap()
+            .add_bytes("memory.bin", vec![1, 2, 3])
+            .unwrap()
+            .write(&pak_path)
+            .unwrap();
+
+        let pak = Pak::open(&pak_path).unwrap();
+        assert_eq!(pak.len(), 3);
+        assert!(pak.contains(dir.join("audio/b.txt")));
+
+        let read = |path: PathBuf| futures_lite::future::block_on(pak.read(path));
+        assert_eq!(read(dir.join("a.txt")).unwrap(), b"first");
+        assert_eq!(read(dir.join("audio/b.txt")).unwrap(), b"second");
+        assert_eq!(read(PathBuf::from("memory.bin")).unwrap(), [1, 2, 3]);
+        assert!(matches!(
+            read(PathBuf::from("missing.bin")),
+            Err(PakError::NotFound(_))
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+        std::fs::remove_file(pak_path).unwrap();
+    }
+
+    #[test]
+    fn open_rejects_other_files() {
+        let path = temp_path("not-a.pak");
+        std::fs::write(&path, b"PNG and some more bytes").unwrap();
+        assert!(matches!(Pak::open(&path), Err(PakError::InvalidMagic)));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn open_rejects_huge_entry_counts() {
+        let path = temp_path("truncated.pak");
+        let mut header = MAGIC.to_vec();
+        header.push(VERSION);
+        header.extend((u32::MAX - 1).to_le_bytes());
+        std::fs::write(&path, header).unwrap();
+        assert!(matches!(Pak::open(&path), Err(PakError::Corrupted(_))));
+        std::fs::remove_file(path).unwrap();
+    }
+}
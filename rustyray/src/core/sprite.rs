@@ -0,0 +1,1016 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Deserialize;
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+use thiserror::Error;
+
+use super::{
+    assets::Handle,
+    color::Color,
+    drawing::Draw,
+    image::Texture,
+    math::{Rectangle, Vector2},
+};
+
+/// Duration of frames that don't specify one, in seconds
+pub const DEFAULT_FRAME_DURATION: f32 = 0.1;
+/// Zero length frames would never let [AnimationPlayer::update] end
+const MIN_FRAME_DURATION: f32 = 0.001;
+
+#[derive(Debug, Error)]
+pub enum SpriteSheetError {
+    #[error("invalid sprite sheet json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("animation {animation:?} references unknown frame {frame:?}")]
+    UnknownFrame { animation: String, frame: String },
+    #[error("frame {0:?} is rotated, export the sheet without rotation")]
+    RotatedFrame(String),
+    #[error("tag {tag:?} covers frames {from}..={to} but the sheet has {len} frames")]
+    TagOutOfRange {
+        tag: String,
+        from: usize,
+        to: usize,
+        len: usize,
+    },
+}
+
+/// One image inside a [SpriteSheet]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteFrame {
+    pub name: Option<String>,
+    /// Pixels of the frame inside the texture
+    pub region: Rectangle,
+    /// Position of `region` inside the untrimmed sprite
+    pub offset: Vector2,
+    /// Size of the untrimmed sprite
+    pub source_size: Vector2,
+    /// Seconds, used by clips built from the sheet
+    pub duration: f32,
+}
+
+impl SpriteFrame {
+    pub fn new(region: Rectangle) -> Self {
+        Self {
+            name: None,
+            region,
+            offset: Vector2::ZERO,
+            source_size: region.size(),
+            duration: DEFAULT_FRAME_DURATION,
+        }
+    }
+}
+
+/// Layout of a uniform grid of frames, read left to right then top to bottom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grid {
+    pub frame_width: f32,
+    pub frame_height: f32,
+    pub columns: u32,
+    pub rows: u32,
+    /// Pixels around the whole grid
+    pub margin: f32,
+    /// Pixels between frames
+    pub spacing: f32,
+}
+
+impl Grid {
+    pub fn new(frame_width: f32, frame_height: f32, columns: u32, rows: u32) -> Self {
+        Self {
+            frame_width,
+            frame_height,
+            columns,
+            rows,
+            margin: 0.0,
+            spacing: 0.0,
+        }
+    }
+
+    pub fn margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+}
+
+/// Frames and named clips sharing one [Texture]
+#[derive(Debug, Clone)]
+pub struct SpriteSheet {
+    texture: Handle<Texture>,
+    frames: Vec<SpriteFrame>,
+    names: HashMap<String, usize>,
+    clips: HashMap<String, AnimationClip>,
+}
+
+impl SpriteSheet {
+    pub fn new(texture: Handle<Texture>, frames: Vec<SpriteFrame>) -> Self {
+        let names = frames
+            .iter()
+            .enumerate()
+            .filter_map(|(index, frame)| Some((frame.name.clone()?, index)))
+            .collect();
+        Self {
+            texture,
+            frames,
+            names,
+            clips: HashMap::new(),
+        }
+    }
+
+    pub fn from_grid(texture: Handle<Texture>, grid: Grid) -> Self {
+        let frames = (0..grid.rows)
+            .flat_map(|row| (0..grid.columns).map(move |column| (row, column)))
+            .map(|(row, column)| {
+                SpriteFrame::new(Rectangle::new(
+                    grid.margin + column as f32 * (grid.frame_width + grid.spacing),
+                    grid.margin + row as f32 * (grid.frame_height + grid.spacing),
+                    grid.frame_width,
+                    grid.frame_height,
+                ))
+            })
+            .collect();
+        Self::new(texture, frames)
+    }
+
+    /// Read the JSON written by Aseprite's "Export Sprite Sheet", hash or array layout
+    ///
+    /// Frame durations come from the file and every tag becomes a clip named after it.
+    pub fn from_aseprite_json(
+        texture: Handle<Texture>,
+        json: &str,
+    ) -> Result<Self, SpriteSheetError> {
+        let file: SheetFile = serde_json::from_str(json)?;
+        let mut sheet = Self::new(texture, file.frames.into_frames()?);
+
+        for tag in file.meta.frame_tags {
+            let len = sheet.frames.len();
+            if tag.from > tag.to || tag.to >= len {
+                return Err(SpriteSheetError::TagOutOfRange {
+                    tag: tag.name,
+                    from: tag.from,
+                    to: tag.to,
+                    len,
+                });
+            }
+            let mut frames: Vec<usize> = (tag.from..=tag.to).collect();
+            let mode = match tag.direction.as_str() {
+                "reverse" => {
+                    frames.reverse();
+                    PlaybackMode::Loop
+                }
+                "pingpong" => PlaybackMode::PingPong,
+                "pingpong_reverse" => {
+                    frames.reverse();
+                    PlaybackMode::PingPong
+                }
+                _ => PlaybackMode::Loop,
+            };
+            let clip = AnimationClip::from_sheet(&sheet, frames).mode(mode);
+            sheet.add_clip(tag.name, clip);
+        }
+        Ok(sheet)
+    }
+
+    /// Read TexturePacker's "JSON (Hash)" or "JSON (Array)" data
+    ///
+    /// The optional top level `animations` map of frame names becomes clips.
+    pub fn from_texture_packer_json(
+        texture: Handle<Texture>,
+        json: &str,
+    ) -> Result<Self, SpriteSheetError> {
+        let file: SheetFile = serde_json::from_str(json)?;
+        let mut sheet = Self::new(texture, file.frames.into_frames()?);
+
+        let mut animations: Vec<_> = file.animations.into_iter().collect();
+        animations.sort_by(|a, b| a.0.cmp(&b.0));
+        for (animation, names) in animations {
+            let frames = names
+                .into_iter()
+                .map(|frame| {
+                    sheet
+                        .frame_index(&frame)
+                        .ok_or_else(|| SpriteSheetError::UnknownFrame {
+                            animation: animation.clone(),
+                            frame,
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let clip = AnimationClip::from_sheet(&sheet, frames);
+            sheet.add_clip(animation, clip);
+        }
+        Ok(sheet)
+    }
+
+    pub fn texture(&self) -> &Handle<Texture> {
+        &self.texture
+    }
+
+    pub fn frames(&self) -> &[SpriteFrame] {
+        &self.frames
+    }
+
+    pub fn frame(&self, index: usize) -> Option<&SpriteFrame> {
+        self.frames.get(index)
+    }
+
+    pub fn frame_index(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn add_clip(&mut self, name: impl Into<String>, clip: AnimationClip) {
+        self.clips.insert(name.into(), clip);
+    }
+
+    pub fn clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+
+    pub fn clips(&self) -> &HashMap<String, AnimationClip> {
+        &self.clips
+    }
+
+    /// Draw frame `index` unscaled with its untrimmed top left corner at `position`
+    pub fn draw_frame(&self, d: &impl Draw, index: usize, position: Vector2, tint: Color) {
+        let Some(frame) = self.frames.get(index) else {
+            return;
+        };
+        let dest = Rectangle::new(
+            position.x,
+            position.y,
+            frame.source_size.x,
+            frame.source_size.y,
+        );
+        self.draw_frame_pro(d, index, dest, Vector2::ZERO, 0.0, tint);
+    }
+
+    /// Draw frame `index` like [Draw::draw_texture_pro], `dest` covers the untrimmed sprite
+    pub fn draw_frame_pro(
+        &self,
+        d: &impl Draw,
+        index: usize,
+        dest: Rectangle,
+        origin: Vector2,
+        rotation: f32,
+        tint: Color,
+    ) {
+        let Some(frame) = self.frames.get(index) else {
+            return;
+        };
+        if frame.source_size.x <= 0.0 || frame.source_size.y <= 0.0 {
+            return;
+        }
+        let scale = Vector2::new(
+            dest.width / frame.source_size.x,
+            dest.height / frame.source_size.y,
+        );
+        // Trimmed frames keep the pivot of the full sprite
+        let origin = Vector2::new(
+            origin.x - frame.offset.x * scale.x,
+            origin.y - frame.offset.y * scale.y,
+        );
+        let dest = Rectangle::new(
+            dest.x,
+            dest.y,
+            frame.region.width * scale.x,
+            frame.region.height * scale.y,
+        );
+        d.draw_texture_pro(&self.texture, frame.region, dest, origin, rotation, tint);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PlaybackMode {
+    /// Start over after the last frame
+    #[default]
+    Loop,
+    /// Run back and forth, the end frames are shown once per pass
+    PingPong,
+    /// Stop on the last frame
+    Once,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipFrame {
+    /// Index into the [SpriteSheet] frames
+    pub frame: usize,
+    /// Seconds
+    pub duration: f32,
+}
+
+/// A sequence of sheet frames with named events attached to some of them
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AnimationClip {
+    pub frames: Vec<ClipFrame>,
+    pub mode: PlaybackMode,
+    /// `(clip frame, name)` pairs, fired when that frame is entered
+    pub events: Vec<(usize, String)>,
+}
+
+impl AnimationClip {
+    /// Every frame lasts `duration` seconds
+    pub fn new(frames: impl IntoIterator<Item = usize>, duration: f32) -> Self {
+        Self {
+            frames: frames
+                .into_iter()
+                .map(|frame| ClipFrame { frame, duration })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Frames keep the durations stored in `sheet`
+    pub fn from_sheet(sheet: &SpriteSheet, frames: impl IntoIterator<Item = usize>) -> Self {
+        Self {
+            frames: frames
+                .into_iter()
+                .map(|frame| ClipFrame {
+                    frame,
+                    duration: sheet
+                        .frame(frame)
+                        .map_or(DEFAULT_FRAME_DURATION, |f| f.duration),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn mode(mut self, mode: PlaybackMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Fire [AnimationEvent::Event] with `name` when clip frame `frame` starts
+    pub fn event(mut self, frame: usize, name: impl Into<String>) -> Self {
+        self.events.push((frame, name.into()));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Seconds for one pass through the frames
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    /// Seconds until a repeating clip is back on the same frame, `None` for [PlaybackMode::Once]
+    fn cycle(&self) -> Option<f32> {
+        let sum = |frames: &[ClipFrame]| -> f32 {
+            frames
+                .iter()
+                .map(|frame| frame.duration.max(MIN_FRAME_DURATION))
+                .sum()
+        };
+        match self.mode {
+            PlaybackMode::Once => None,
+            PlaybackMode::Loop => Some(sum(&self.frames)),
+            // Forward over every frame, then back over the inner ones
+            PlaybackMode::PingPong => Some(
+                sum(&self.frames)
+                    + self
+                        .frames
+                        .get(1..self.frames.len().saturating_sub(1))
+                        .map_or(0.0, sum),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnimationEvent {
+    /// A named event attached with [AnimationClip::event]
+    Event { clip: String, name: String },
+    /// A looping clip wrapped around, or a ping-pong clip got back to its first frame
+    Looped(String),
+    /// A [PlaybackMode::Once] clip reached its end
+    Finished(String),
+}
+
+/// Plays named [AnimationClip]s and reports [AnimationEvent]s
+///
+/// ```no_run
+/// # use rustyray::prelude::*;
+/// # fn run(window: &mut Window, sheet: &SpriteSheet) {
+/// let mut player = AnimationPlayer::from_sheet(sheet);
+/// player.play("walk");
+/// player.update(window.frame_time());
+/// for event in player.drain_events() {
+///     if let AnimationEvent::Event { name, .. } = event {
+///         println!("{name}");
+///     }
+/// }
+/// window.draw(|d| player.draw(&d, sheet, Vector2::new(10.0, 10.0), Color::WHITE));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnimationPlayer {
+    clips: HashMap<String, AnimationClip>,
+    current: Option<String>,
+    /// Position inside the current clip
+    index: usize,
+    time: f32,
+    forward: bool,
+    finished: bool,
+    paused: bool,
+    speed: f32,
+    events: Vec<AnimationEvent>,
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> Self {
+        Self {
+            clips: HashMap::new(),
+            current: None,
+            index: 0,
+            time: 0.0,
+            forward: true,
+            finished: false,
+            paused: false,
+            speed: 1.0,
+            events: Vec::new(),
+        }
+    }
+}
+
+impl AnimationPlayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with every clip defined on `sheet`
+    pub fn from_sheet(sheet: &SpriteSheet) -> Self {
+        Self {
+            clips: sheet.clips.clone(),
+            ..Default::default()
+        }
+    }
+
+    pub fn add_clip(&mut self, name: impl Into<String>, clip: AnimationClip) {
+        self.clips.insert(name.into(), clip);
+    }
+
+    pub fn clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+
+    /// Switch to clip `name`, returns `false` if there is no such clip
+    ///
+    /// Playing the clip that is already running keeps its progress, use [AnimationPlayer::restart]
+    /// to start it over.
+    pub fn play(&mut self, name: &str) -> bool {
+        if !self.clips.contains_key(name) {
+            return false;
+        }
+        if self.current.as_deref() != Some(name) {
+            self.current = Some(name.to_string());
+            self.restart();
+        }
+        self.paused = false;
+        true
+    }
+
+    pub fn restart(&mut self) {
+        self.index = 0;
+        self.time = 0.0;
+        self.forward = true;
+        self.finished = false;
+        self.enter_frame();
+    }
+
+    pub fn stop(&mut self) {
+        self.current = None;
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Playback rate, 1.0 by default
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// `true` once a [PlaybackMode::Once] clip reached its end
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Position inside the current clip
+    pub fn clip_frame(&self) -> usize {
+        self.index
+    }
+
+    /// Sheet frame to draw, `None` when nothing is playing
+    pub fn frame(&self) -> Option<usize> {
+        let clip = self.clips.get(self.current.as_deref()?)?;
+        clip.frames.get(self.index).map(|frame| frame.frame)
+    }
+
+    /// Advance by `delta` seconds
+    pub fn update(&mut self, delta: f32) {
+        if self.paused || self.finished {
+            return;
+        }
+        let Some(clip) = self
+            .current
+            .as_deref()
+            .and_then(|name| self.clips.get(name))
+        else {
+            return;
+        };
+        if clip.frames.is_empty() {
+            return;
+        }
+
+        self.time += delta * self.speed;
+        if !self.time.is_finite() {
+            self.time = 0.0;
+        }
+        // Skip whole cycles at once so a long stall doesn't step through every frame
+        if let Some(cycle) = clip.cycle()
+            && self.time >= cycle
+        {
+            self.time %= cycle;
+            let name = self.current.clone().unwrap();
+            self.events.push(AnimationEvent::Looped(name));
+        }
+        loop {
+            let clip = &self.clips[self.current.as_deref().unwrap()];
+            let duration = clip.frames[self.index].duration.max(MIN_FRAME_DURATION);
+            if self.time < duration {
+                break;
+            }
+            self.time -= duration;
+            if !self.advance() {
+                self.time = 0.0;
+                break;
+            }
+        }
+    }
+
+    /// Step to the next frame, returns `false` once a [PlaybackMode::Once] clip is finished
+    fn advance(&mut self) -> bool {
+        let name = self.current.clone().unwrap();
+        let clip = &self.clips[&name];
+        let last = clip.frames.len() - 1;
+
+        match clip.mode {
+            PlaybackMode::Loop => {
+                if self.index == last {
+                    self.index = 0;
+                    self.events.push(AnimationEvent::Looped(name));
+                } else {
+                    self.index += 1;
+                }
+            }
+            PlaybackMode::Once => {
+                if self.index == last {
+                    self.finished = true;
+                    self.events.push(AnimationEvent::Finished(name));
+                    return false;
+                }
+                self.index += 1;
+            }
+            PlaybackMode::PingPong => {
+                if last == 0 {
+                    self.events.push(AnimationEvent::Looped(name));
+                } else if self.forward {
+                    if self.index == last {
+                        self.forward = false;
+                        self.index -= 1;
+                    } else {
+                        self.index += 1;
+                    }
+                } else if self.index == 0 {
+                    self.forward = true;
+                    self.index = 1;
+                } else {
+                    self.index -= 1;
+                    if self.index == 0 {
+                        self.events.push(AnimationEvent::Looped(name));
+                    }
+                }
+            }
+        }
+        self.enter_frame();
+        true
+    }
+
+    fn enter_frame(&mut self) {
+        let Some(name) = self.current.as_deref() else {
+            return;
+        };
+        let clip = &self.clips[name];
+        for (frame, event) in &clip.events {
+            if *frame == self.index {
+                self.events.push(AnimationEvent::Event {
+                    clip: name.to_string(),
+                    name: event.clone(),
+                });
+            }
+        }
+    }
+
+    /// Take the events fired since the last call
+    pub fn drain_events(&mut self) -> impl Iterator<Item = AnimationEvent> + '_ {
+        self.events.drain(..)
+    }
+
+    /// Draw the current frame unscaled at `position`
+    pub fn draw(&self, d: &impl Draw, sheet: &SpriteSheet, position: Vector2, tint: Color) {
+        if let Some(frame) = self.frame() {
+            sheet.draw_frame(d, frame, position, tint);
+        }
+    }
+
+    /// Draw the current frame like [Draw::draw_texture_pro]
+    pub fn draw_pro(
+        &self,
+        d: &impl Draw,
+        sheet: &SpriteSheet,
+        dest: Rectangle,
+        origin: Vector2,
+        rotation: f32,
+        tint: Color,
+    ) {
+        if let Some(frame) = self.frame() {
+            sheet.draw_frame_pro(d, frame, dest, origin, rotation, tint);
+        }
+    }
+}
+
+/// Shared layout of Aseprite and TexturePacker JSON files
+#[derive(Deserialize)]
+struct SheetFile {
+    frames: SheetFrames,
+    #[serde(default)]
+    meta: SheetMeta,
+    #[serde(default)]
+    animations: HashMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct SheetMeta {
+    #[serde(default)]
+    frame_tags: Vec<FrameTag>,
+}
+
+#[derive(Deserialize)]
+struct FrameTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: String,
+}
+
+#[derive(Deserialize)]
+struct JsonRect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct JsonSize {
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFrame {
+    #[serde(default)]
+    filename: Option<String>,
+    frame: JsonRect,
+    #[serde(default)]
+    rotated: bool,
+    #[serde(default)]
+    sprite_source_size: Option<JsonRect>,
+    #[serde(default)]
+    source_size: Option<JsonSize>,
+    /// Milliseconds
+    #[serde(default)]
+    duration: Option<f32>,
+}
+
+enum SheetFrames {
+    Array(Vec<JsonFrame>),
+    /// Keeps the file order, tags refer to frames by position
+    Hash(Vec<(String, JsonFrame)>),
+}
+
+impl SheetFrames {
+    fn into_frames(self) -> Result<Vec<SpriteFrame>, SpriteSheetError> {
+        let named: Vec<(Option<String>, JsonFrame)> = match self {
+            SheetFrames::Array(frames) => frames
+                .into_iter()
+                .map(|frame| (frame.filename.clone(), frame))
+                .collect(),
+            SheetFrames::Hash(frames) => frames
+                .into_iter()
+                .map(|(name, frame)| (Some(name), frame))
+                .collect(),
+        };
+
+        named
+            .into_iter()
+            .map(|(name, frame)| {
+                if frame.rotated {
+                    return Err(SpriteSheetError::RotatedFrame(name.unwrap_or_default()));
+                }
+                let region =
+                    Rectangle::new(frame.frame.x, frame.frame.y, frame.frame.w, frame.frame.h);
+                Ok(SpriteFrame {
+                    name,
+                    region,
+                    offset: frame
+                        .sprite_source_size
+                        .map_or(Vector2::ZERO, |trim| Vector2::new(trim.x, trim.y)),
+                    source_size: frame
+                        .source_size
+                        .map_or(region.size(), |size| Vector2::new(size.w, size.h)),
+                    duration: frame
+                        .duration
+                        .map_or(DEFAULT_FRAME_DURATION, |ms| ms / 1000.0),
+                })
+            })
+            .collect()
+    }
+}
+
+impl<'de> Deserialize<'de> for SheetFrames {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FramesVisitor;
+
+        impl<'de> Visitor<'de> for FramesVisitor {
+            type Value = SheetFrames;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an array or a map of frames")
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::new();
+                while let Some(frame) = seq.next_element()? {
+                    frames.push(frame);
+                }
+                Ok(SheetFrames::Array(frames))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    frames.push(entry);
+                }
+                Ok(SheetFrames::Hash(frames))
+            }
+        }
+
+        deserializer.deserialize_any(FramesVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::assets::AssetManager;
+
+    fn texture(manager: &mut AssetManager) -> Handle<Texture> {
+        manager.dummy_handle::<Texture>()
+    }
+
+    #[test]
+    fn grid_frames_follow_rows() {
+        let mut manager = AssetManager::new();
+        let sheet = SpriteSheet::from_grid(
+            texture(&mut manager),
+            Grid::new(16.0, 8.0, 3, 2).margin(1.0).spacing(2.0),
+        );
+        assert_eq!(sheet.len(), 6);
+        assert_eq!(
+            sheet.frames()[1].region,
+            Rectangle::new(19.0, 1.0, 16.0, 8.0)
+        );
+        assert_eq!(
+            sheet.frames()[3].region,
+            Rectangle::new(1.0, 11.0, 16.0, 8.0)
+        );
+    }
+
+    #[test]
+    fn reads_aseprite_hash_with_tags() {
+        let json = r#"{
+            "frames": {
+                "walk 2.ase": { "frame": {"x": 32, "y": 0, "w": 16, "h": 16}, "duration": 200 },
+                "walk 0.ase": {
+                    "frame": {"x": 0, "y": 0, "w": 14, "h": 15},
+                    "rotated": false, "trimmed": true,
+                    "spriteSourceSize": {"x": 1, "y": 1, "w": 14, "h": 15},
+                    "sourceSize": {"w": 16, "h": 16},
+                    "duration": 100
+                },
+                "walk 1.ase": { "frame": {"x": 16, "y": 0, "w": 16, "h": 16}, "duration": 150 }
+            },
+            "meta": {
+                "frameTags": [
+                    { "name": "idle", "from": 0, "to": 0, "direction": "forward" },
+                    { "name": "walk", "from": 0, "to": 2, "direction": "pingpong_reverse" }
+                ]
+            }
+        }"#;
+        let mut manager = AssetManager::new();
+        let sheet = SpriteSheet::from_aseprite_json(texture(&mut manager), json).unwrap();
+
+        // File order is kept even though the names sort differently
+        assert_eq!(sheet.frame_index("walk 2.ase"), Some(0));
+        let trimmed = sheet.frame(1).unwrap();
+        assert_eq!(trimmed.offset, Vector2::new(1.0, 1.0));
+        assert_eq!(trimmed.source_size, Vector2::new(16.0, 16.0));
+
+        let walk = sheet.clip("walk").unwrap();
+        assert_eq!(walk.mode, PlaybackMode::PingPong);
+        let frames: Vec<_> = walk.frames.iter().map(|f| (f.frame, f.duration)).collect();
+        assert_eq!(frames, [(2, 0.15), (1, 0.1), (0, 0.2)]);
+        assert_eq!(sheet.clip("idle").unwrap().len(), 1);
+
+        let bad = json.replace(r#""to": 2"#, r#""to": 3"#);
+        assert!(matches!(
+            SpriteSheet::from_aseprite_json(texture(&mut manager), &bad),
+            Err(SpriteSheetError::TagOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn reads_texture_packer_array_with_animations() {
+        let json = r#"{
+            "frames": [
+                { "filename": "run_0", "frame": {"x": 0, "y": 0, "w": 8, "h": 8}, "rotated": false },
+                { "filename": "run_1", "frame": {"x": 8, "y": 0, "w": 8, "h": 8}, "rotated": false }
+            ],
+            "animations": { "run": ["run_1", "run_0"] },
+            "meta": { "app": "https://www.codeandweb.com/texturepacker", "size": {"w": 16, "h": 8} }
+        }"#;
+        let mut manager = AssetManager::new();
+        let sheet = SpriteSheet::from_texture_packer_json(texture(&mut manager), json).unwrap();
+        assert_eq!(sheet.frame_index("run_1"), Some(1));
+        let run: Vec<_> = sheet
+            .clip("run")
+            .unwrap()
+            .frames
+            .iter()
+            .map(|f| f.frame)
+            .collect();
+        assert_eq!(run, [1, 0]);
+
+        let missing = json.replace(r#"["run_1", "run_0"]"#, r#"["run_2"]"#);
+        assert!(matches!(
+            SpriteSheet::from_texture_packer_json(texture(&mut manager), &missing),
+            Err(SpriteSheetError::UnknownFrame { .. })
+        ));
+        let rotated = json.replacen(r#""rotated": false"#, r#""rotated": true"#, 1);
+        assert!(matches!(
+            SpriteSheet::from_texture_packer_json(texture(&mut manager), &rotated),
+            Err(SpriteSheetError::RotatedFrame(_))
+        ));
+    }
+
+    fn frames_over(player: &mut AnimationPlayer, steps: usize, delta: f32) -> Vec<usize> {
+        (0..steps)
+            .map(|_| {
+                player.update(delta);
+                player.frame().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn player_modes() {
+        let mut player = AnimationPlayer::new();
+        player.add_clip("loop", AnimationClip::new([10, 11, 12], 1.0));
+        player.add_clip(
+            "pingpong",
+            AnimationClip::new([10, 11, 12], 1.0).mode(PlaybackMode::PingPong),
+        );
+        player.add_clip(
+            "once",
+            AnimationClip::new([10, 11, 12], 1.0).mode(PlaybackMode::Once),
+        );
+        assert!(!player.play("missing"));
+
+        player.play("loop");
+        assert_eq!(frames_over(&mut player, 4, 1.0), [11, 12, 10, 11]);
+        assert_eq!(
+            player.drain_events().collect::<Vec<_>>(),
+            [AnimationEvent::Looped("loop".into())]
+        );
+
+        player.play("pingpong");
+        assert_eq!(frames_over(&mut player, 6, 1.0), [11, 12, 11, 10, 11, 12]);
+        assert_eq!(player.drain_events().count(), 1);
+
+        player.play("once");
+        // A long frame skips ahead over several frames
+        player.update(2.5);
+        assert_eq!(player.frame(), Some(12));
+        player.update(5.0);
+        assert!(player.is_finished());
+        assert_eq!(player.frame(), Some(12));
+        assert_eq!(
+            player.drain_events().collect::<Vec<_>>(),
+            [AnimationEvent::Finished("once".into())]
+        );
+    }
+
+    #[test]
+    fn player_skips_whole_cycles_on_huge_deltas() {
+        let mut player = AnimationPlayer::new();
+        player.add_clip(
+            "loop",
+            AnimationClip::new([10, 11, 12], 0.0).event(1, "step"),
+        );
+        player.add_clip(
+            "pingpong",
+            AnimationClip::new([10, 11, 12], 1.0).mode(PlaybackMode::PingPong),
+        );
+
+        player.play("loop");
+        player.update(1e9);
+        player.update(f32::INFINITY);
+        assert!(player.drain_events().count() <= 8);
+        assert!(player.frame().is_some());
+
+        // A ping-pong cycle is 4 seconds, 10 cycles plus 1 second lands on the second frame
+        player.play("pingpong");
+        player.update(41.0);
+        assert_eq!(player.frame(), Some(11));
+        assert_eq!(
+            player.drain_events().collect::<Vec<_>>(),
+            [AnimationEvent::Looped("pingpong".into())]
+        );
+    }
+
+    #[test]
+    fn player_fires_frame_events_and_respects_speed() {
+        let mut player = AnimationPlayer::new();
+        let clip = AnimationClip::new([0, 1, 2, 3], 0.1)
+            .event(0, "start")
+            .event(2, "step");
+        player.add_clip("walk", clip);
+        player.play("walk");
+        player.set_speed(2.0);
+        player.update(0.1);
+        assert_eq!(player.clip_frame(), 2);
+
+        let names: Vec<_> = player
+            .drain_events()
+            .map(|event| match event {
+                AnimationEvent::Event { name, .. } => name,
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(names, ["start", "step"]);
+
+        // Replaying the running clip doesn't restart it
+        player.play("walk");
+        assert_eq!(player.clip_frame(), 2);
+        player.pause();
+        player.update(1.0);
+        assert_eq!(player.clip_frame(), 2);
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use crate::core::image::Image;
+use crate::core::math::Rectangle;
+
+use super::FontError;
+
+/// Largest accepted `scaleW`, `scaleH` and stacked atlas height
+const MAX_PAGE_SIZE: i32 = 16384;
+
+/// One `char` line of a BMFont file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BmChar {
+    pub id: u32,
+    /// Position in the stacked page atlas, see [BmFont::atlas]
+    pub region: Rectangle,
+    pub offset_x: i32,
+    pub offset_y: i32,
+    pub advance: i32,
+}
+
+/// Text format BMFont (`.fnt`) description, the binary and XML variants are not supported
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BmFont {
+    pub line_height: i32,
+    pub page_width: i32,
+    pub page_height: i32,
+    /// Page image files, relative to the `.fnt` file
+    pub pages: Vec<String>,
+    pub chars: Vec<BmChar>,
+}
+
+/// Split `key=value` pairs of a line, values may be quoted and contain spaces
+fn attributes(line: &str) -> HashMap<&str, &str> {
+    let mut attributes = HashMap::new();
+    let mut rest = line.trim_start();
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        let after = &rest[eq + 1..];
+        let (value, remaining) = if let Some(quoted) = after.strip_prefix('"') {
+            let end = quoted.find('"').unwrap_or(quoted.len());
+            (&quoted[..end], quoted.get(end + 1..).unwrap_or(""))
+        } else {
+            let end = after.find(char::is_whitespace).unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        attributes.insert(key, value);
+        rest = remaining.trim_start();
+    }
+    attributes
+}
+
+fn number(attributes: &HashMap<&str, &str>, key: &str) -> Result<i32, FontError> {
+    attributes
+        .get(key)
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| FontError::BmFont(format!("missing or invalid `{key}`")))
+}
+
+impl BmFont {
+    pub fn parse(text: &str) -> Result<Self, FontError> {
+        let mut common = None;
+        let mut pages: Vec<(i32, String)> = Vec::new();
+        let mut chars = Vec::new();
+
+        for line in text.lines() {
+            let (tag, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+            let attributes = attributes(rest);
+            match tag {
+                "common" => {
+                    common = Some((
+                        number(&attributes, "lineHeight")?,
+                        number(&attributes, "scaleW")?,
+                        number(&attributes, "scaleH")?,
+                    ));
+                }
+                "page" => {
+                    let file = attributes
+                        .get("file")
+                        .ok_or_else(|| FontError::BmFont(String::from("page without a file")))?;
+                    pages.push((number(&attributes, "id")?, file.to_string()));
+                }
+                "char" => chars.push((
+                    number(&attributes, "page").unwrap_or(0),
+                    number(&attributes, "id")?,
+                    [
+                        number(&attributes, "x")?,
+                        number(&attributes, "y")?,
+                        number(&attributes, "width")?,
+                        number(&attributes, "height")?,
+                        number(&attributes, "xoffset")?,
+                        number(&attributes, "yoffset")?,
+                        number(&attributes, "xadvance")?,
+                    ],
+                )),
+                _ => {}
+            }
+        }
+
+        let (line_height, page_width, page_height) =
+            common.ok_or_else(|| FontError::BmFont(String::from("missing `common` line")))?;
+        if !(1..=MAX_PAGE_SIZE).contains(&line_height)
+            || !(1..=MAX_PAGE_SIZE).contains(&page_width)
+            || !(1..=MAX_PAGE_SIZE).contains(&page_height)
+        {
+            return Err(FontError::BmFont(format!(
+                "`lineHeight`, `scaleW` and `scaleH` must be between 1 and {MAX_PAGE_SIZE}"
+            )));
+        }
+        pages.sort_by_key(|(id, _)| *id);
+        if pages.is_empty() || pages.iter().enumerate().any(|(i, (id, _))| *id != i as i32) {
+            return Err(FontError::BmFont(String::from(
+                "pages must be numbered from 0 without gaps",
+            )));
+        }
+        if pages.len() as i64 * page_height as i64 > MAX_PAGE_SIZE as i64 {
+            return Err(FontError::BmFont(format!(
+                "{} pages do not fit in a {MAX_PAGE_SIZE} pixel atlas",
+                pages.len()
+            )));
+        }
+
+        let chars = chars
+            .into_iter()
+            .map(
+                |(page, id, [x, y, width, height, offset_x, offset_y, advance])| {
+                    if page < 0 || page as usize >= pages.len() {
+                        return Err(FontError::BmFont(format!(
+                            "char {id} uses missing page {page}"
+                        )));
+                    }
+                    Ok(BmChar {
+                        id: id as u32,
+                        region: Rectangle::new(
+                            x as f32,
+                            y as f32 + (page * page_height) as f32,
+                            width as f32,
+                            height as f32,
+                        ),
+                        offset_x,
+                        offset_y,
+                        advance,
+                    })
+                },
+            )
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            line_height,
+            page_width,
+            page_height,
+            pages: pages.into_iter().map(|(_, file)| file).collect(),
+            chars,
+        })
+    }
+
+    /// Stack the page images top to bottom, the char regions already point into the result
+    pub fn atlas(&self, pages: &[Image]) -> Result<Image, FontError> {
+        if pages.len() != self.pages.len() {
+            return Err(FontError::BmFont(format!(
+                "expected {} page images, got {}",
+                self.pages.len(),
+                pages.len()
+            )));
+        }
+        let too_large = || FontError::BmFont(String::from("page atlas is too large"));
+        let height = i32::try_from(pages.len())
+            .ok()
+            .and_then(|count| self.page_height.checked_mul(count))
+            .ok_or_else(too_large)?;
+        let len = (self.page_width.max(0) as usize)
+            .checked_mul(height.max(0) as usize)
+            .ok_or_else(too_large)?;
+        let mut pixels = Vec::with_capacity(len);
+        for (page, file) in pages.iter().zip(&self.pages) {
+            if page.width() != self.page_width || page.height() != self.page_height {
+                return Err(FontError::BmFont(format!(
+                    "page {file} is {}x{}, expected {}x{}",
+                    page.width(),
+                    page.height(),
+                    self.page_width,
+                    self.page_height
+                )));
+            }
+            pixels.extend(page.to_colors());
+        }
+        Ok(Image::from_colors(self.page_width, height, pixels)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::color::Color;
+
+    const FNT: &str = r#"info face="Pixel Sans" size=16 bold=0 italic=0 charset="" unicode=1
+common lineHeight=18 base=14 scaleW=64 scaleH=32 pages=2 packed=0
+page id=1 file="pixel_1.png"
+page id=0 file="pixel 0.png"
+chars count=3
+char id=65   x=0  y=0  width=8  height=12 xoffset=0 yoffset=2 xadvance=9 page=0 chnl=15
+char id=233  x=8  y=4  width=8  height=14 xoffset=1 yoffset=0 xadvance=9 page=1 chnl=15
+char id=32   x=0  y=0  width=0  height=0  xoffset=0 yoffset=0 xadvance=5 page=0 chnl=15
+"#;
+
+    #[test]
+    fn parses_text_bmfont() {
+        let font = BmFont::parse(FNT).unwrap();
+        assert_eq!(font.line_height, 18);
+        assert_eq!(font.pages, ["pixel 0.png", "pixel_1.png"]);
+        assert_eq!(font.chars.len(), 3);
+
+        let accent = font.chars[1];
+        assert_eq!(char::from_u32(accent.id), Some('é'));
+        // Second page sits below the first in the atlas
+        assert_eq!(accent.region, Rectangle::new(8.0, 36.0, 8.0, 14.0));
+        assert_eq!((accent.offset_x, accent.advance), (1, 9));
+
+        let pages = vec![Image::new(64, 32, Color::new(255, 0, 0, 255)); 2];
+        let atlas = font.atlas(&pages).unwrap();
+        assert_eq!((atlas.width(), atlas.height()), (64, 64));
+        assert!(
+            font.atlas(&[Image::new(32, 32, Color::new(0, 0, 0, 0))])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_broken_files() {
+        assert!(BmFont::parse("info face=\"x\"\n").is_err());
+        let missing_page = "common lineHeight=8 scaleW=8 scaleH=8\npage id=0 file=\"a.png\"\nchar id=65 x=0 y=0 width=1 height=1 xoffset=0 yoffset=0 xadvance=1 page=3\n";
+        assert!(BmFont::parse(missing_page).is_err());
+        let huge_page =
+            "common lineHeight=8 scaleW=2147483647 scaleH=2147483647\npage id=0 file=\"a.png\"\n";
+        assert!(BmFont::parse(huge_page).is_err());
+        let no_height = "common lineHeight=0 scaleW=8 scaleH=8\npage id=0 file=\"a.png\"\n";
+        assert!(BmFont::parse(no_height).is_err());
+    }
+}
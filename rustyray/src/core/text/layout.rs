@@ -0,0 +1,722 @@
+use std::ops::Range;
+
+use crate::core::color::Color;
+use crate::core::math::{Rectangle, Vector2};
+
+use super::{Font, TEXT_LINE_SPACING};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Stretch spaces so wrapped lines fill the width, the last line of a paragraph stays left
+    Justify,
+}
+
+/// Inline style of a piece of [RichText]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    /// `None` uses the tint passed when drawing
+    pub color: Option<Color>,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+/// Text with styled byte ranges
+///
+/// [RichText::parse] reads a small markup: `[color=#rrggbb]`, `[color=#rrggbbaa]`, `[u]` for
+/// underline and `[s]` for strikethrough, each closed with `[/color]`, `[/u]` and `[/s]`. Write
+/// `[[` for a literal `[`, anything that isn't a known tag is kept as text.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RichText {
+    text: String,
+    spans: Vec<(Range<usize>, TextStyle)>,
+}
+
+impl From<&str> for RichText {
+    fn from(text: &str) -> Self {
+        Self::new().with(text, TextStyle::default())
+    }
+}
+
+impl From<String> for RichText {
+    fn from(text: String) -> Self {
+        Self::from(text.as_str())
+    }
+}
+
+fn parse_color(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#')?;
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    match hex.len() {
+        6 => Some(Color::new(channel(0)?, channel(2)?, channel(4)?, 255)),
+        8 => Some(Color::new(
+            channel(0)?,
+            channel(2)?,
+            channel(4)?,
+            channel(6)?,
+        )),
+        _ => None,
+    }
+}
+
+impl RichText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(markup: &str) -> Self {
+        let mut rich = Self::new();
+        let mut colors = Vec::new();
+        let (mut underline, mut strikethrough) = (0u32, 0u32);
+        let mut rest = markup;
+
+        while !rest.is_empty() {
+            let style = TextStyle {
+                color: colors.last().copied(),
+                underline: underline > 0,
+                strikethrough: strikethrough > 0,
+            };
+            if let Some(after) = rest.strip_prefix("[[") {
+                rich.push("[", style);
+                rest = after;
+                continue;
+            }
+            if let Some(tag) = rest.strip_prefix('[').and_then(|r| r.split_once(']')) {
+                let (name, after) = tag;
+                let known = match name {
+                    "u" => {
+                        underline += 1;
+                        true
+                    }
+                    "/u" => {
+                        underline = underline.saturating_sub(1);
+                        true
+                    }
+                    "s" => {
+                        strikethrough += 1;
+                        true
+                    }
+                    "/s" => {
+                        strikethrough = strikethrough.saturating_sub(1);
+                        true
+                    }
+                    "/color" => {
+                        colors.pop();
+                        true
+                    }
+                    _ => match name.strip_prefix("color=").and_then(parse_color) {
+                        Some(color) => {
+                            colors.push(color);
+                            true
+                        }
+                        None => false,
+                    },
+                };
+                if known {
+                    rest = after;
+                    continue;
+                }
+            }
+            // Plain text up to the next possible tag
+            let end = rest
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == '[')
+                .map_or(rest.len(), |(i, _)| i);
+            rich.push(&rest[..end], style);
+            rest = &rest[end..];
+        }
+        rich
+    }
+
+    /// Append `text` with `style`, merging with the previous span when the style matches
+    pub fn push(&mut self, text: &str, style: TextStyle) {
+        if text.is_empty() {
+            return;
+        }
+        let start = self.text.len();
+        self.text.push_str(text);
+        match self.spans.last_mut() {
+            Some((range, last)) if *last == style => range.end = self.text.len(),
+            _ => self.spans.push((start..self.text.len(), style)),
+        }
+    }
+
+    pub fn with(mut self, text: &str, style: TextStyle) -> Self {
+        self.push(text, style);
+        self
+    }
+
+    /// The text without markup
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn spans(&self) -> &[(Range<usize>, TextStyle)] {
+        &self.spans
+    }
+
+    pub fn style_at(&self, index: usize) -> TextStyle {
+        self.spans
+            .iter()
+            .find(|(range, _)| range.contains(&index))
+            .map_or(TextStyle::default(), |(_, style)| *style)
+    }
+}
+
+/// A character placed by [Paragraph::layout]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedGlyph {
+    pub codepoint: char,
+    /// Byte offset in [TextLayout::text], ellipsis glyphs use the offset the text was cut at
+    pub index: usize,
+    /// Top left of the glyph's cell, pass it to [Draw::draw_text_codepoint](crate::core::drawing::Draw::draw_text_codepoint)
+    pub position: Vector2,
+    pub advance: f32,
+    pub style: TextStyle,
+}
+
+/// Consecutive glyphs of one line sharing a [TextStyle]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphRun {
+    /// Range of [TextLayout::glyphs]
+    pub glyphs: Range<usize>,
+    pub style: TextStyle,
+    pub bounds: Rectangle,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutLine {
+    /// Range of [TextLayout::glyphs]
+    pub glyphs: Range<usize>,
+    /// Bytes of [TextLayout::text] shown on this line, without the whitespace it wrapped at
+    pub text: Range<usize>,
+    pub bounds: Rectangle,
+}
+
+/// How to lay text out inside a rectangle, see [Paragraph::layout]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paragraph {
+    /// A width of 0 or less never wraps, a height of 0 or less never truncates
+    pub bounds: Rectangle,
+    pub size: f32,
+    /// Added between characters, like the `spacing` of [Draw::draw_text_ex](crate::core::drawing::Draw::draw_text_ex)
+    pub spacing: f32,
+    /// Added between lines
+    pub line_spacing: f32,
+    pub align: TextAlign,
+    pub wrap: bool,
+    /// End cut off text with `…`, or `...` when the font has no such glyph
+    pub ellipsis: bool,
+    pub max_lines: Option<usize>,
+}
+
+/// A line before it is positioned, indices into the paragraph's chars
+struct RawLine {
+    chars: Range<usize>,
+    /// Last line of its paragraph
+    hard_break: bool,
+    ellipsis: bool,
+}
+
+impl Paragraph {
+    pub fn new(bounds: Rectangle, size: f32) -> Self {
+        Self {
+            bounds,
+            size,
+            spacing: 0.0,
+            line_spacing: TEXT_LINE_SPACING,
+            align: TextAlign::Left,
+            wrap: true,
+            ellipsis: false,
+            max_lines: None,
+        }
+    }
+
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn line_spacing(mut self, line_spacing: f32) -> Self {
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn ellipsis(mut self, ellipsis: bool) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    fn line_height(&self) -> f32 {
+        self.size + self.line_spacing
+    }
+
+    /// Lines that fit in the bounds, at least one
+    fn line_limit(&self) -> usize {
+        let by_height = if self.bounds.height > 0.0 {
+            ((self.bounds.height + self.line_spacing) / self.line_height()).floor() as usize
+        } else {
+            usize::MAX
+        };
+        by_height.min(self.max_lines.unwrap_or(usize::MAX)).max(1)
+    }
+
+    /// Width of `chars` without trailing whitespace
+    fn width(&self, advances: &[f32], chars: &[(usize, char)], range: Range<usize>) -> f32 {
+        let end = trim_end(chars, range.clone());
+        let count = end - range.start;
+        advances[range.start..end].iter().sum::<f32>()
+            + count.saturating_sub(1) as f32 * self.spacing
+    }
+
+    /// Split paragraphs into lines, wrapping at whitespace or inside words wider than a line
+    fn break_lines(&self, chars: &[(usize, char)], advances: &[f32]) -> Vec<RawLine> {
+        let max_width = self.bounds.width;
+        let wrapping = self.wrap && max_width > 0.0;
+        let mut lines = Vec::new();
+        let mut start = 0;
+
+        loop {
+            let paragraph_end = chars[start..]
+                .iter()
+                .position(|&(_, c)| c == '\n')
+                .map_or(chars.len(), |i| start + i);
+
+            let mut line_start = start;
+            loop {
+                let mut width = 0.0;
+                let mut last_space = None;
+                let mut end = paragraph_end;
+                let mut next = paragraph_end;
+                for i in line_start..paragraph_end {
+                    let c = chars[i].1;
+                    let gap = if i > line_start { self.spacing } else { 0.0 };
+                    let new_width = width + gap + advances[i];
+                    if wrapping && new_width > max_width && i > line_start && !c.is_whitespace() {
+                        match last_space {
+                            Some(space) => (end, next) = (space, space + 1),
+                            None => (end, next) = (i, i),
+                        }
+                        break;
+                    }
+                    if c.is_whitespace() {
+                        last_space = Some(i);
+                    }
+                    width = new_width;
+                }
+                while next < paragraph_end && chars[next].1.is_whitespace() {
+                    next += 1;
+                }
+
+                let hard_break = next >= paragraph_end;
+                lines.push(RawLine {
+                    chars: line_start..end,
+                    hard_break,
+                    ellipsis: false,
+                });
+                if hard_break {
+                    break;
+                }
+                line_start = next;
+            }
+
+            if paragraph_end >= chars.len() {
+                return lines;
+            }
+            start = paragraph_end + 1;
+        }
+    }
+
+    /// Lay `text` out with the metrics of `font`
+    pub fn layout(&self, font: &Font, text: impl Into<RichText>) -> TextLayout {
+        let rich = text.into();
+        let chars: Vec<_> = rich.text().char_indices().collect();
+        let advances: Vec<_> = chars
+            .iter()
+            .map(|&(_, c)| font.advance(c, self.size))
+            .collect();
+
+        let mut lines = self.break_lines(&chars, &advances);
+        let limit = self.line_limit();
+        let truncated = lines.len() > limit;
+        lines.truncate(limit);
+        if truncated && let Some(last) = lines.last_mut() {
+            last.hard_break = true;
+            last.ellipsis = self.ellipsis;
+        }
+        for line in &mut lines {
+            let width = self.width(&advances, &chars, line.chars.clone());
+            if self.bounds.width > 0.0 && width > self.bounds.width {
+                line.ellipsis = self.ellipsis;
+            }
+        }
+
+        let ellipsis = if font.has_glyph('…') { "…" } else { "..." };
+        let ellipsis_width = font.measure(ellipsis, self.size, self.spacing).x;
+
+        let mut layout = TextLayout {
+            glyphs: Vec::new(),
+            runs: Vec::new(),
+            lines: Vec::new(),
+            size: self.size,
+            truncated,
+            text: rich,
+        };
+        let line_height = self.line_height();
+        let end_of_text = layout.text.text().len();
+        let byte_at = |i: usize| chars.get(i).map_or(end_of_text, |&(byte, _)| byte);
+
+        for (row, line) in lines.iter().enumerate() {
+            let mut end = trim_end(&chars, line.chars.clone());
+            let mut width = self.width(&advances, &chars, line.chars.start..end);
+            if line.ellipsis {
+                if self.bounds.width > 0.0 {
+                    // Drop characters until the ellipsis fits after them
+                    while end > line.chars.start
+                        && width + self.spacing + ellipsis_width > self.bounds.width
+                    {
+                        end = trim_end(&chars, line.chars.start..end - 1);
+                        width = self.width(&advances, &chars, line.chars.start..end);
+                    }
+                }
+                if end > line.chars.start {
+                    width += self.spacing;
+                }
+                width += ellipsis_width;
+            }
+
+            let visible = line.chars.start..end;
+            let spaces = chars[visible.clone()]
+                .iter()
+                .filter(|(_, c)| c.is_whitespace())
+                .count();
+            let free = (self.bounds.width - width).max(0.0);
+            let justify = self.align == TextAlign::Justify
+                && !line.hard_break
+                && !line.ellipsis
+                && spaces > 0
+                && self.bounds.width > 0.0;
+            let offset = match self.align {
+                TextAlign::Left | TextAlign::Justify => 0.0,
+                TextAlign::Center => free / 2.0,
+                TextAlign::Right => free,
+            };
+            let stretch = if justify { free / spaces as f32 } else { 0.0 };
+
+            let y = self.bounds.y + row as f32 * line_height;
+            let x = self.bounds.x + offset;
+            let mut pen = x;
+            let first = layout.glyphs.len();
+            for i in visible.clone() {
+                let (index, codepoint) = chars[i];
+                layout.glyphs.push(PositionedGlyph {
+                    codepoint,
+                    index,
+                    position: Vector2::new(pen, y),
+                    advance: advances[i],
+                    style: layout.text.style_at(index),
+                });
+                pen += advances[i] + self.spacing;
+                if codepoint.is_whitespace() {
+                    pen += stretch;
+                }
+            }
+            if line.ellipsis {
+                let cut = byte_at(end);
+                let style = layout.glyphs[first..]
+                    .last()
+                    .map_or(layout.text.style_at(cut), |glyph| glyph.style);
+                for codepoint in ellipsis.chars() {
+                    let advance = font.advance(codepoint, self.size);
+                    layout.glyphs.push(PositionedGlyph {
+                        codepoint,
+                        index: cut,
+                        position: Vector2::new(pen, y),
+                        advance,
+                        style,
+                    });
+                    pen += advance + self.spacing;
+                }
+            }
+
+            let glyphs = first..layout.glyphs.len();
+            layout.push_runs(glyphs.clone());
+            layout.lines.push(LayoutLine {
+                glyphs,
+                text: byte_at(visible.start)..byte_at(visible.end),
+                bounds: Rectangle::new(
+                    x,
+                    y,
+                    if justify { self.bounds.width } else { width },
+                    self.size,
+                ),
+            });
+        }
+        layout
+    }
+}
+
+/// End of `range` with trailing whitespace removed
+fn trim_end(chars: &[(usize, char)], range: Range<usize>) -> usize {
+    let mut end = range.end;
+    while end > range.start && chars[end - 1].1.is_whitespace() {
+        end -= 1;
+    }
+    end
+}
+
+/// Positioned lines and glyphs of a [Paragraph], measured for hit-testing and caret placement
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    text: RichText,
+    glyphs: Vec<PositionedGlyph>,
+    runs: Vec<GlyphRun>,
+    lines: Vec<LayoutLine>,
+    size: f32,
+    truncated: bool,
+}
+
+impl TextLayout {
+    fn push_runs(&mut self, glyphs: Range<usize>) {
+        let mut start = glyphs.start;
+        for i in glyphs.clone() {
+            let ends_run = i + 1 == glyphs.end || self.glyphs[i + 1].style != self.glyphs[i].style;
+            if !ends_run {
+                continue;
+            }
+            let (first, last) = (&self.glyphs[start], &self.glyphs[i]);
+            let width = last.position.x + last.advance - first.position.x;
+            self.runs.push(GlyphRun {
+                glyphs: start..i + 1,
+                style: first.style,
+                bounds: Rectangle::new(
+                    first.position.x,
+                    first.position.y,
+                    width.max(0.0),
+                    self.size,
+                ),
+            });
+            start = i + 1;
+        }
+    }
+
+    /// The laid out text without markup
+    pub fn text(&self) -> &str {
+        self.text.text()
+    }
+
+    pub fn rich_text(&self) -> &RichText {
+        &self.text
+    }
+
+    pub fn glyphs(&self) -> &[PositionedGlyph] {
+        &self.glyphs
+    }
+
+    pub fn runs(&self) -> &[GlyphRun] {
+        &self.runs
+    }
+
+    pub fn lines(&self) -> &[LayoutLine] {
+        &self.lines
+    }
+
+    /// Font size the text was laid out at, also the height of a caret
+    pub fn font_size(&self) -> f32 {
+        self.size
+    }
+
+    /// Lines were dropped because they didn't fit
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Smallest rectangle around every line
+    pub fn bounds(&self) -> Rectangle {
+        let Some(first) = self.lines.first() else {
+            return Rectangle::new(0.0, 0.0, 0.0, 0.0);
+        };
+        let (mut min, mut max) = (first.bounds.position(), first.bounds.position());
+        for line in &self.lines {
+            let bounds = line.bounds;
+            min = Vector2::new(min.x.min(bounds.x), min.y.min(bounds.y));
+            max = Vector2::new(
+                max.x.max(bounds.x + bounds.width),
+                max.y.max(bounds.y + bounds.height),
+            );
+        }
+        Rectangle::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    pub fn size(&self) -> Vector2 {
+        self.bounds().size()
+    }
+
+    /// Byte offset of the caret position closest to `point`
+    pub fn hit_test(&self, point: Vector2) -> usize {
+        let Some(line) = self
+            .lines
+            .iter()
+            .find(|line| point.y < line.bounds.y + line.bounds.height)
+            .or(self.lines.last())
+        else {
+            return 0;
+        };
+        self.glyphs[line.glyphs.clone()]
+            .iter()
+            .find(|glyph| point.x < glyph.position.x + glyph.advance / 2.0)
+            .map_or(line.text.end, |glyph| glyph.index)
+    }
+
+    /// Top of the caret placed before the character at byte `index`, `size` tall
+    pub fn caret_position(&self, index: usize) -> Vector2 {
+        let Some(line) = self
+            .lines
+            .iter()
+            .find(|line| index <= line.text.end)
+            .or(self.lines.last())
+        else {
+            return Vector2::ZERO;
+        };
+        let glyphs = &self.glyphs[line.glyphs.clone()];
+        match glyphs.iter().find(|glyph| glyph.index >= index) {
+            Some(glyph) => glyph.position,
+            None => Vector2::new(line.bounds.x + line.bounds.width, line.bounds.y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::test_font;
+    use super::*;
+
+    /// Every glyph is 10 wide at size 10, no `…`
+    fn mono() -> Font {
+        let glyphs: Vec<_> = (' '..='~').map(|c| (c, 10.0)).collect();
+        test_font(&glyphs, 10)
+    }
+
+    fn line_text(layout: &TextLayout, line: usize) -> String {
+        layout.glyphs()[layout.lines()[line].glyphs.clone()]
+            .iter()
+            .map(|glyph| glyph.codepoint)
+            .collect()
+    }
+
+    #[test]
+    fn parses_markup() {
+        let red = Color::new(255, 0, 0, 255);
+        let rich = RichText::parse("[color=#ff0000]red [u]both[/color] under[/u] [[x] [b]");
+        assert_eq!(rich.text(), "red both under [x] [b]");
+        assert_eq!(rich.style_at(0).color, Some(red));
+        assert_eq!(
+            rich.style_at(4),
+            TextStyle {
+                color: Some(red),
+                underline: true,
+                strikethrough: false
+            }
+        );
+        assert_eq!(rich.style_at(9).color, None);
+        assert!(rich.style_at(9).underline);
+        assert_eq!(rich.spans().len(), 4);
+    }
+
+    #[test]
+    fn wraps_and_aligns() {
+        let font = mono();
+        let bounds = Rectangle::new(0.0, 0.0, 75.0, 0.0);
+        let layout = Paragraph::new(bounds, 10.0).layout(&font, "aaa bbb ccc\n\nabcdefgh");
+        let lines: Vec<_> = (0..layout.lines().len())
+            .map(|i| line_text(&layout, i))
+            .collect();
+        assert_eq!(lines, ["aaa bbb", "ccc", "", "abcdefg", "h"]);
+        assert_eq!(layout.lines()[1].text, 8..11);
+        assert_eq!(layout.lines()[3].bounds.y, 36.0);
+        assert_eq!(layout.size(), Vector2::new(70.0, 58.0));
+
+        let right = Paragraph::new(bounds, 10.0)
+            .align(TextAlign::Right)
+            .layout(&font, "aaa bbb ccc");
+        assert_eq!(right.glyphs()[7].position, Vector2::new(45.0, 12.0));
+        let center = Paragraph::new(bounds, 10.0)
+            .align(TextAlign::Center)
+            .layout(&font, "aaa bbb ccc");
+        assert_eq!(center.glyphs()[0].position.x, 2.5);
+
+        let justified = Paragraph::new(Rectangle::new(0.0, 0.0, 100.0, 0.0), 10.0)
+            .align(TextAlign::Justify)
+            .layout(&font, "aa bb cc dd");
+        // 20 free pixels spread over 2 spaces
+        assert_eq!(justified.glyphs()[6].position.x, 80.0);
+        assert_eq!(justified.lines()[0].bounds.width, 100.0);
+        assert_eq!(justified.lines()[1].bounds.width, 20.0);
+    }
+
+    #[test]
+    fn truncates_with_ellipsis() {
+        let font = mono();
+        let layout = Paragraph::new(Rectangle::new(0.0, 0.0, 75.0, 15.0), 10.0)
+            .ellipsis(true)
+            .layout(&font, "aaa bbb ccc");
+        assert!(layout.is_truncated());
+        assert_eq!(layout.lines().len(), 1);
+        assert_eq!(line_text(&layout, 0), "aaa...");
+        assert_eq!(layout.lines()[0].text, 0..3);
+
+        let unwrapped = Paragraph::new(Rectangle::new(0.0, 0.0, 75.0, 0.0), 10.0)
+            .wrap(false)
+            .ellipsis(true)
+            .layout(&font, "abcdefghij");
+        assert_eq!(line_text(&unwrapped, 0), "abcd...");
+        assert!(!unwrapped.is_truncated());
+
+        let limited = Paragraph::new(Rectangle::new(0.0, 0.0, 75.0, 0.0), 10.0)
+            .max_lines(2)
+            .layout(&font, "aaa bbb ccc ddd eee");
+        assert_eq!(line_text(&limited, 1), "ccc ddd");
+    }
+
+    #[test]
+    fn hit_tests_and_places_carets() {
+        let font = mono();
+        let layout = Paragraph::new(Rectangle::new(10.0, 20.0, 75.0, 0.0), 10.0)
+            .layout(&font, RichText::parse("[color=#00ff00]aaa[/color] bbb ccc"));
+        assert_eq!(layout.text(), "aaa bbb ccc");
+        assert_eq!(layout.runs().len(), 3);
+        assert_eq!(
+            layout.runs()[0].bounds,
+            Rectangle::new(10.0, 20.0, 30.0, 10.0)
+        );
+
+        assert_eq!(layout.hit_test(Vector2::new(44.0, 25.0)), 3);
+        assert_eq!(layout.hit_test(Vector2::new(46.0, 25.0)), 4);
+        assert_eq!(layout.hit_test(Vector2::new(500.0, 25.0)), 7);
+        assert_eq!(layout.hit_test(Vector2::new(22.0, 500.0)), 9);
+
+        assert_eq!(layout.caret_position(4), Vector2::new(50.0, 20.0));
+        assert_eq!(layout.caret_position(7), Vector2::new(80.0, 20.0));
+        assert_eq!(layout.caret_position(8), Vector2::new(10.0, 32.0));
+        assert_eq!(layout.caret_position(11), Vector2::new(40.0, 32.0));
+    }
+}
@@ -0,0 +1,608 @@
+use std::collections::HashMap;
+use std::ffi::{CString, c_int};
+use std::path::Path;
+
+use rustyray_sys::{
+    consts::{PixelFormat, TextureFilter},
+    ffi::{
+        get_font_default, is_window_ready, load_font_from_memory, load_texture_from_image,
+        mem_alloc, set_texture_filter, unload_font,
+    },
+    math::Rectangle as RayRectangle,
+    text::{Font as RayFont, GlyphInfo},
+    texture::Image as RayImage,
+};
+use thiserror::Error;
+
+use super::assets::{Asset, AssetLoader, LoadContext};
+use super::color::Color;
+use super::image::{AtlasError, Image, ImageError, texture_size};
+use super::math::{Rectangle, Vector2};
+
+mod bmfont;
+mod layout;
+mod sdf;
+
+use bmfont::BmFont;
+pub use layout::*;
+pub use sdf::SdfEffects;
+use sdf::{Coverage, sdf_atlas};
+pub(crate) use sdf::{unload_sdf_shader, with_font_shader};
+
+/// Size glyphs are rasterized at when a [FontKey] doesn't choose one, same as raylib
+pub const DEFAULT_FONT_SIZE: i32 = 32;
+/// Extra space raylib puts between lines of multi-line text
+pub const TEXT_LINE_SPACING: f32 = 2.0;
+
+#[derive(Debug, Error)]
+pub enum FontError {
+    #[error("file not found: {0}")]
+    FileNotFound(String),
+    #[error("unsupported font file {0}, expected .ttf, .otf or .fnt")]
+    UnsupportedFormat(String),
+    #[error("invalid BMFont file: {0}")]
+    BmFont(String),
+    #[error(transparent)]
+    Image(#[from] ImageError),
+    #[error("failed to pack the SDF atlas: {0}")]
+    Atlas(#[from] AtlasError),
+    #[error("you must first create a Window before loading fonts")]
+    WindowNotReady,
+    #[error("failed to load font {0}")]
+    LoadFailed(String),
+}
+
+/// Font file and how to bake it, loaded with [AssetManager::load](super::assets::AssetManager::load)
+///
+/// `size` and `glyphs` only apply to TTF/OTF files, a BMFont ships with its own.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FontKey {
+    pub path: String,
+    /// Pixel height glyphs are rasterized at, drawing at this size is the sharpest
+    pub size: i32,
+    /// Codepoints to bake, empty bakes raylib's default printable ASCII set
+    pub glyphs: Vec<char>,
+    /// `None` keeps raylib's default, [TextureFilter::Point], or bilinear for SDF fonts
+    pub filter: Option<TextureFilter>,
+    /// Bake a signed distance field reaching this many pixels around each glyph, see [FontKey::sdf]
+    pub sdf: Option<i32>,
+}
+
+impl FontKey {
+    pub fn new(path: impl Into<String>, size: i32) -> Self {
+        Self {
+            path: path.into(),
+            size,
+            glyphs: Vec::new(),
+            filter: None,
+            sdf: None,
+        }
+    }
+
+    /// Add codepoints to bake, e.g. `"çãõ".chars()` or `'А'..='я'`
+    ///
+    /// Once any are added only those are baked, chain [FontKey::ascii] to keep ASCII too.
+    pub fn glyphs(mut self, glyphs: impl IntoIterator<Item = char>) -> Self {
+        self.glyphs.extend(glyphs);
+        self.glyphs.sort_unstable();
+        self.glyphs.dedup();
+        self
+    }
+
+    /// Add printable ASCII, `' '` to `'~'`
+    pub fn ascii(self) -> Self {
+        self.glyphs(' '..='~')
+    }
+
+    pub fn filter(mut self, filter: TextureFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Bake a signed distance field instead of a bitmap, so text stays sharp at any scale
+    ///
+    /// `spread` is how far in pixels the field reaches past the glyph edges, it also caps the
+    /// width of [SdfEffects] outlines and glows. 4 to 8 works well, bake at 48px or more.
+    pub fn sdf(mut self, spread: i32) -> Self {
+        self.sdf = Some(spread.max(1));
+        self
+    }
+}
+
+impl From<String> for FontKey {
+    fn from(path: String) -> Self {
+        Self::new(path, DEFAULT_FONT_SIZE)
+    }
+}
+
+impl From<&str> for FontKey {
+    fn from(path: &str) -> Self {
+        Self::new(path, DEFAULT_FONT_SIZE)
+    }
+}
+
+impl From<(&str, i32)> for FontKey {
+    fn from((path, size): (&str, i32)) -> Self {
+        Self::new(path, size)
+    }
+}
+
+/// Placement of one character in a [Font] texture, in pixels at [Font::base_size]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    pub codepoint: char,
+    /// Area of the font texture holding the glyph
+    pub region: Rectangle,
+    /// Where to draw `region` relative to the pen position
+    pub offset: Vector2,
+    /// How far the pen moves after this glyph
+    pub advance: f32,
+}
+
+pub struct Font {
+    inner: RayFont,
+    glyphs: Vec<Glyph>,
+    index: HashMap<char, usize>,
+    /// Glyph used for missing codepoints, `'?'` when the font has it
+    fallback: usize,
+    /// The default font belongs to raylib and must not be unloaded
+    owned: bool,
+    sdf_spread: Option<i32>,
+}
+
+impl std::fmt::Debug for Font {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Font")
+            .field("base_size", &self.inner.base_size)
+            .field("glyphs", &self.glyphs.len())
+            .field("texture", &self.inner.texture.id)
+            .finish()
+    }
+}
+
+impl Font {
+    /// raylib's built-in font, the one [Draw::draw_text](super::drawing::Draw::draw_text) uses
+    pub fn default_font() -> Result<Self, FontError> {
+        if !unsafe { is_window_ready() } {
+            return Err(FontError::WindowNotReady);
+        }
+        Ok(Self::from_ray(unsafe { get_font_default() }, false))
+    }
+
+    fn from_ray(inner: RayFont, owned: bool) -> Self {
+        let count = inner.glyph_count.max(0) as usize;
+        let glyphs = if inner.glyphs.is_null() || inner.recs.is_null() {
+            Vec::new()
+        } else {
+            let (infos, recs) = unsafe {
+                (
+                    std::slice::from_raw_parts(inner.glyphs, count),
+                    std::slice::from_raw_parts(inner.recs, count),
+                )
+            };
+            infos
+                .iter()
+                .zip(recs)
+                .map(|(info, &rec)| Glyph {
+                    codepoint: char::from_u32(info.value as u32).unwrap_or('\u{FFFD}'),
+                    region: rec.into(),
+                    offset: Vector2::new(info.offset_x as f32, info.offset_y as f32),
+                    // Same rule raylib uses when advancing the pen
+                    advance: if info.advance_x > 0 {
+                        info.advance_x as f32
+                    } else {
+                        rec.width + info.offset_x as f32
+                    },
+                })
+                .collect()
+        };
+        Self::with_glyphs(inner, glyphs, owned)
+    }
+
+    fn with_glyphs(inner: RayFont, glyphs: Vec<Glyph>, owned: bool) -> Self {
+        let index: HashMap<_, _> = glyphs
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, glyph)| (glyph.codepoint, i))
+            .collect();
+        let fallback = index.get(&'?').copied().unwrap_or(0);
+        Self {
+            inner,
+            glyphs,
+            index,
+            fallback,
+            owned,
+            sdf_spread: None,
+        }
+    }
+
+    pub(crate) fn as_ray(&self) -> RayFont {
+        self.inner.clone()
+    }
+
+    /// Pixel height the glyphs were baked at
+    pub fn base_size(&self) -> i32 {
+        self.inner.base_size
+    }
+
+    pub fn glyphs(&self) -> &[Glyph] {
+        &self.glyphs
+    }
+
+    pub fn has_glyph(&self, codepoint: char) -> bool {
+        self.index.contains_key(&codepoint)
+    }
+
+    /// Glyph drawn for `codepoint`, missing ones fall back to `'?'` like raylib does
+    pub fn glyph(&self, codepoint: char) -> Option<&Glyph> {
+        let index = self.index.get(&codepoint).copied().unwrap_or(self.fallback);
+        self.glyphs.get(index)
+    }
+
+    /// Pen advance of `codepoint` when drawn at `size`
+    pub fn advance(&self, codepoint: char, size: f32) -> f32 {
+        self.glyph(codepoint).map_or(0.0, |glyph| {
+            glyph.advance * size / self.base_size().max(1) as f32
+        })
+    }
+
+    /// Size of `text` drawn with [Draw::draw_text_ex](super::drawing::Draw::draw_text_ex)
+    pub fn measure(&self, text: &str, size: f32, spacing: f32) -> Vector2 {
+        if text.is_empty() {
+            return Vector2::ZERO;
+        }
+        let mut width: f32 = 0.0;
+        let mut lines = 0;
+        for line in text.split('\n') {
+            lines += 1;
+            let (advance, count) = line.chars().fold((0.0, 0), |(advance, count), c| {
+                (advance + self.advance(c, size), count + 1)
+            });
+            let gaps = (count as f32 - 1.0).max(0.0);
+            width = width.max(advance + gaps * spacing);
+        }
+        let height = size + (lines - 1) as f32 * (size + TEXT_LINE_SPACING);
+        Vector2::new(width, height)
+    }
+
+    pub fn set_filter(&self, filter: TextureFilter) {
+        unsafe { set_texture_filter(self.inner.texture.clone(), filter) }
+    }
+
+    /// Glyphs are a signed distance field, drawn through the SDF shader
+    pub fn is_sdf(&self) -> bool {
+        self.sdf_spread.is_some()
+    }
+
+    /// Pixels the distance field reaches past the glyph edges
+    pub fn sdf_spread(&self) -> Option<i32> {
+        self.sdf_spread
+    }
+
+    /// Coverage of each glyph from the CPU copies raylib keeps of TTF/OTF glyphs
+    fn glyph_coverage(&self) -> Vec<Coverage> {
+        let count = self.inner.glyph_count.max(0) as usize;
+        if self.inner.glyphs.is_null() {
+            return Vec::new();
+        }
+        let infos = unsafe { std::slice::from_raw_parts(self.inner.glyphs, count) };
+        infos
+            .iter()
+            .map(|info| {
+                let image = &info.image;
+                let pixels = image.pixel_format().ok().and_then(|format| {
+                    if image.data.is_null() {
+                        return None;
+                    }
+                    let size = format.data_size(image.width, image.height);
+                    let bytes = unsafe { std::slice::from_raw_parts(image.data.cast(), size) };
+                    let image = Image::from_raw(image.width, image.height, format, bytes.to_vec());
+                    let alpha = |c: Color| match format {
+                        PixelFormat::UncompressedGrayscale => c.r,
+                        _ => c.a,
+                    };
+                    image
+                        .ok()
+                        .map(|image| image.to_colors().into_iter().map(alpha).collect())
+                });
+                match pixels {
+                    Some(alpha) => Coverage {
+                        width: image.width,
+                        height: image.height,
+                        alpha,
+                    },
+                    None => Coverage {
+                        width: 0,
+                        height: 0,
+                        alpha: Vec::new(),
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+impl Drop for Font {
+    fn drop(&mut self) {
+        if self.owned {
+            unsafe { unload_font(self.inner.clone()) }
+        }
+    }
+}
+
+impl Asset for Font {
+    fn size_estimate(&self) -> usize {
+        if self.owned {
+            texture_size(&self.inner.texture)
+        } else {
+            0
+        }
+    }
+}
+
+enum FontSource {
+    TrueType { bytes: Vec<u8>, extension: String },
+    BmFont { font: BmFont, atlas: Image },
+}
+
+/// Font file read and decoded on a worker thread, turned into a [Font] on the main thread
+pub struct FontData {
+    source: FontSource,
+    key: FontKey,
+}
+
+impl AssetLoader for Font {
+    type Key = FontKey;
+    type Error = FontError;
+    type Decoded = FontData;
+
+    async fn decode(key: Self::Key, ctx: LoadContext) -> Result<Self::Decoded, Self::Error> {
+        let bytes = ctx
+            .read(&key.path)
+            .await
+            .map_err(|_| FontError::FileNotFound(key.path.clone()))?;
+        let extension = Path::new(&key.path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+
+        let source = match extension.as_str() {
+            "ttf" | "otf" => FontSource::TrueType { bytes, extension },
+            "fnt" => {
+                let text = String::from_utf8(bytes)
+                    .map_err(|_| FontError::BmFont(String::from("only text BMFont files")))?;
+                let font = BmFont::parse(&text)?;
+                let dir = Path::new(&key.path).parent().unwrap_or(Path::new(""));
+                let mut pages = Vec::with_capacity(font.pages.len());
+                for page in &font.pages {
+                    let path = dir.join(page);
+                    let bytes = ctx
+                        .read(&path)
+                        .await
+                        .map_err(|_| FontError::FileNotFound(path.display().to_string()))?;
+                    let extension = path.extension().and_then(|ext| ext.to_str());
+                    pages.push(Image::from_memory(&bytes, extension.unwrap_or("png"))?);
+                }
+                let atlas = font.atlas(&pages)?;
+                FontSource::BmFont { font, atlas }
+            }
+            _ => return Err(FontError::UnsupportedFormat(key.path)),
+        };
+        Ok(FontData { source, key })
+    }
+
+    fn finish(FontData { source, key }: Self::Decoded) -> Result<Self, Self::Error> {
+        if !unsafe { is_window_ready() } {
+            return Err(FontError::WindowNotReady);
+        }
+        let failed = || FontError::LoadFailed(key.path.clone());
+        let mut font = match (source, key.sdf) {
+            (FontSource::TrueType { bytes, extension }, None) => {
+                load_true_type(&bytes, &extension, &key)?
+            }
+            (FontSource::TrueType { bytes, extension }, Some(spread)) => {
+                // Rasterize normally, then turn the glyph bitmaps into distance fields
+                let bitmap = load_true_type(&bytes, &extension, &key)?;
+                let coverage = bitmap.glyph_coverage();
+                let (atlas, glyphs) = sdf_atlas(bitmap.glyphs(), &coverage, spread)?;
+                build_font(bitmap.base_size(), &atlas, &glyphs).ok_or_else(failed)?
+            }
+            (FontSource::BmFont { font, atlas }, None) => {
+                build_font(font.line_height, &atlas, &bmfont_glyphs(&font)).ok_or_else(failed)?
+            }
+            (FontSource::BmFont { font, atlas }, Some(spread)) => {
+                let glyphs = bmfont_glyphs(&font);
+                let (atlas, glyphs) = sdf_atlas(&glyphs, &atlas_coverage(&atlas, &glyphs), spread)?;
+                build_font(font.line_height, &atlas, &glyphs).ok_or_else(failed)?
+            }
+        };
+
+        font.sdf_spread = key.sdf;
+        match (key.filter, key.sdf) {
+            (Some(filter), _) => font.set_filter(filter),
+            (None, Some(_)) => font.set_filter(TextureFilter::Bilinear),
+            (None, None) => {}
+        }
+        Ok(font)
+    }
+
+    fn path(key: &Self::Key) -> Option<&Path> {
+        Some(Path::new(&key.path))
+    }
+}
+
+fn load_true_type(bytes: &[u8], extension: &str, key: &FontKey) -> Result<Font, FontError> {
+    let failed = || FontError::LoadFailed(key.path.clone());
+    let file_type = CString::new(format!(".{extension}")).map_err(|_| failed())?;
+    let size = c_int::try_from(bytes.len()).map_err(|_| failed())?;
+    let codepoints: Vec<c_int> = key.glyphs.iter().map(|&c| c as c_int).collect();
+    let codepoints_ptr = if codepoints.is_empty() {
+        std::ptr::null()
+    } else {
+        codepoints.as_ptr()
+    };
+
+    let inner = unsafe {
+        load_font_from_memory(
+            file_type.as_ptr(),
+            bytes.as_ptr(),
+            size,
+            key.size,
+            codepoints_ptr,
+            codepoints.len() as c_int,
+        )
+    };
+    if inner.texture.id == 0 || inner.glyphs.is_null() {
+        return Err(failed());
+    }
+    Ok(Font::from_ray(inner, true))
+}
+
+fn bmfont_glyphs(font: &BmFont) -> Vec<Glyph> {
+    font.chars
+        .iter()
+        .map(|char| Glyph {
+            codepoint: char::from_u32(char.id).unwrap_or('\u{FFFD}'),
+            region: char.region,
+            offset: Vector2::new(char.offset_x as f32, char.offset_y as f32),
+            advance: char.advance as f32,
+        })
+        .collect()
+}
+
+/// Alpha of every glyph region of `atlas`
+fn atlas_coverage(atlas: &Image, glyphs: &[Glyph]) -> Vec<Coverage> {
+    let colors = atlas.to_colors();
+    glyphs
+        .iter()
+        .map(|glyph| {
+            let Rectangle {
+                x,
+                y,
+                width,
+                height,
+            } = glyph.region;
+            let (x, y, width, height) = (x as i32, y as i32, width as i32, height as i32);
+            let inside = x >= 0
+                && y >= 0
+                && width >= 0
+                && height >= 0
+                && x.checked_add(width)
+                    .is_some_and(|right| right <= atlas.width())
+                && y.checked_add(height)
+                    .is_some_and(|bottom| bottom <= atlas.height());
+            let (width, height) = if inside { (width, height) } else { (0, 0) };
+            let alpha = (y..y + height)
+                .flat_map(|row| (x..x + width).map(move |col| (row, col)))
+                .map(|(row, col)| colors[(row * atlas.width() + col) as usize].a)
+                .collect();
+            Coverage {
+                width,
+                height,
+                alpha,
+            }
+        })
+        .collect()
+}
+
+/// Upload `atlas` as a raylib font, with buffers from raylib's allocator so `UnloadFont` can free them
+fn build_font(base_size: i32, atlas: &Image, glyphs: &[Glyph]) -> Option<Font> {
+    let count = glyphs.len();
+    let texture = unsafe { load_texture_from_image(atlas.as_ray()) };
+    if texture.id == 0 || count == 0 {
+        return None;
+    }
+
+    let (recs, infos) = unsafe {
+        (
+            mem_alloc((count * size_of::<RayRectangle>()) as u32).cast::<RayRectangle>(),
+            mem_alloc((count * size_of::<GlyphInfo>()) as u32).cast::<GlyphInfo>(),
+        )
+    };
+    let inner = RayFont {
+        base_size,
+        glyph_count: count as c_int,
+        glyph_padding: 0,
+        texture,
+        recs,
+        glyphs: infos,
+    };
+    if recs.is_null() || infos.is_null() {
+        // Frees the texture and whichever buffer was allocated
+        unsafe { unload_font(inner) };
+        return None;
+    }
+
+    for (i, glyph) in glyphs.iter().enumerate() {
+        unsafe {
+            recs.add(i).write(glyph.region.into());
+            infos.add(i).write(GlyphInfo {
+                value: glyph.codepoint as c_int,
+                offset_x: glyph.offset.x as c_int,
+                offset_y: glyph.offset.y as c_int,
+                advance_x: glyph.advance as c_int,
+                // Glyph images are only needed for CPU text rendering
+                image: RayImage {
+                    data: std::ptr::null_mut(),
+                    width: 0,
+                    height: 0,
+                    mipmaps: 1,
+                    format: PixelFormat::UncompressedR8G8B8A8 as c_int,
+                },
+            });
+        }
+    }
+    Some(Font::from_ray(inner, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A font with only CPU side metrics, enough for measuring
+    pub(crate) fn test_font(glyphs: &[(char, f32)], base_size: i32) -> Font {
+        let inner = RayFont {
+            base_size,
+            glyph_count: 0,
+            glyph_padding: 0,
+            texture: unsafe { std::mem::zeroed() },
+            recs: std::ptr::null_mut(),
+            glyphs: std::ptr::null_mut(),
+        };
+        let glyphs = glyphs
+            .iter()
+            .map(|&(codepoint, advance)| Glyph {
+                codepoint,
+                region: Rectangle::new(0.0, 0.0, advance, base_size as f32),
+                offset: Vector2::ZERO,
+                advance,
+            })
+            .collect();
+        Font::with_glyphs(inner, glyphs, false)
+    }
+
+    #[test]
+    fn keys_collect_unique_glyphs() {
+        let key = FontKey::from(("fonts/noto.ttf", 48))
+            .glyphs("ação".chars())
+            .glyphs(['ç'])
+            .filter(TextureFilter::Bilinear);
+        assert_eq!(key.size, 48);
+        assert_eq!(key.glyphs, ['a', 'o', 'ã', 'ç']);
+        assert_eq!(FontKey::from("f.ttf").ascii().glyphs.len(), 95);
+    }
+
+    #[test]
+    fn measures_like_raylib() {
+        let font = test_font(&[('?', 6.0), ('a', 8.0), ('é', 10.0), (' ', 4.0)], 16);
+        assert_eq!(font.measure("", 16.0, 1.0), Vector2::ZERO);
+        // 8 + 10 + 4 + 8 advances and 3 gaps of spacing
+        assert_eq!(font.measure("aé a", 16.0, 1.0), Vector2::new(33.0, 16.0));
+        // Scaled to twice the base size, the missing glyph uses '?'
+        assert_eq!(font.measure("a\nzz", 32.0, 0.0), Vector2::new(24.0, 66.0));
+        assert!(!font.has_glyph('z'));
+        assert_eq!(font.glyph('z').unwrap().codepoint, '?');
+    }
+}
@@ -0,0 +1,377 @@
+use std::cell::RefCell;
+use std::ffi::{CStr, c_int};
+
+use rustyray_sys::{
+    consts::{GlVersion, ShaderUniformDataType},
+    ffi::{
+        begin_shader_mode, end_shader_mode, get_shader_location, load_shader_from_memory,
+        rl_get_version, set_shader_value, unload_shader,
+    },
+    shader::Shader,
+};
+
+use crate::core::color::Color;
+use crate::core::image::{AtlasBuilder, Image};
+use crate::core::math::{Rectangle, Vector2};
+
+use super::{Font, FontError, Glyph};
+
+const SDF_FS_330: &CStr = cr#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+uniform float outlineWidth;
+uniform vec4 outlineColor;
+uniform float glowWidth;
+uniform vec4 glowColor;
+out vec4 finalColor;
+
+void main()
+{
+    // 0.0 on the glyph edge, positive inside
+    float dist = texture(texture0, fragTexCoord).a - 0.5;
+    float aa = max(length(vec2(dFdx(dist), dFdy(dist))), 0.0001);
+    float edge = dist + outlineWidth;
+    vec4 text = fragColor*colDiffuse;
+    // Coverage goes into alpha once per layer, then text over outline over glow premultiplied
+    float textAlpha = text.a*smoothstep(-aa, aa, dist);
+    float outlineAlpha = outlineColor.a*smoothstep(-aa, aa, edge);
+    float glowAlpha = glowWidth > 0.0 ? glowColor.a*(1.0 - smoothstep(0.0, glowWidth, -edge)) : 0.0;
+    vec4 color = vec4(glowColor.rgb*glowAlpha, glowAlpha);
+    color = vec4(outlineColor.rgb*outlineAlpha, outlineAlpha) + (1.0 - outlineAlpha)*color;
+    color = vec4(text.rgb*textAlpha, textAlpha) + (1.0 - textAlpha)*color;
+    finalColor = vec4(color.rgb/max(color.a, 0.0001), color.a);
+}
+"#;
+
+/// GLSL 100 and 120 body, prefixed with the matching header
+const SDF_FS_100: &str = r#"
+varying vec2 fragTexCoord;
+varying vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+uniform float outlineWidth;
+uniform vec4 outlineColor;
+uniform float glowWidth;
+uniform vec4 glowColor;
+
+void main()
+{
+    float dist = texture2D(texture0, fragTexCoord).a - 0.5;
+    float aa = max(length(vec2(dFdx(dist), dFdy(dist))), 0.0001);
+    float edge = dist + outlineWidth;
+    vec4 text = fragColor*colDiffuse;
+    // Coverage goes into alpha once per layer, then text over outline over glow premultiplied
+    float textAlpha = text.a*smoothstep(-aa, aa, dist);
+    float outlineAlpha = outlineColor.a*smoothstep(-aa, aa, edge);
+    float glowAlpha = glowWidth > 0.0 ? glowColor.a*(1.0 - smoothstep(0.0, glowWidth, -edge)) : 0.0;
+    vec4 color = vec4(glowColor.rgb*glowAlpha, glowAlpha);
+    color = vec4(outlineColor.rgb*outlineAlpha, outlineAlpha) + (1.0 - outlineAlpha)*color;
+    color = vec4(text.rgb*textAlpha, textAlpha) + (1.0 - textAlpha)*color;
+    gl_FragColor = vec4(color.rgb/max(color.a, 0.0001), color.a);
+}
+"#;
+
+/// Outline and glow drawn around [Font::is_sdf] text, widths are in pixels at [Font::base_size]
+///
+/// Outline plus glow can't reach further than the font's SDF spread.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SdfEffects {
+    pub outline_width: f32,
+    pub outline_color: Color,
+    pub glow_width: f32,
+    pub glow_color: Color,
+}
+
+impl SdfEffects {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn outline(mut self, width: f32, color: Color) -> Self {
+        self.outline_width = width;
+        self.outline_color = color;
+        self
+    }
+
+    pub fn glow(mut self, width: f32, color: Color) -> Self {
+        self.glow_width = width;
+        self.glow_color = color;
+        self
+    }
+}
+
+/// Glyph bitmap as 0-255 coverage, row by row
+pub(crate) struct Coverage {
+    pub width: i32,
+    pub height: i32,
+    pub alpha: Vec<u8>,
+}
+
+const FAR: f64 = 1e20;
+
+/// Squared distances of one row or column to its nearest zero, Felzenszwalb and Huttenlocher
+fn edt_1d(f: &[f64], d: &mut [f64], v: &mut [usize], z: &mut [f64]) {
+    let parabola = |q: usize, p: usize| {
+        ((f[q] + (q * q) as f64) - (f[p] + (p * p) as f64)) / (2.0 * q as f64 - 2.0 * p as f64)
+    };
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = -FAR;
+    z[1] = FAR;
+    for q in 1..f.len() {
+        let mut s = parabola(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = parabola(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = FAR;
+    }
+    k = 0;
+    for (q, d) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let dq = q as f64 - v[k] as f64;
+        *d = dq * dq + f[v[k]];
+    }
+}
+
+/// Squared distance of every cell to the nearest `true` one
+fn edt(features: &[bool], width: usize, height: usize) -> Vec<f64> {
+    let mut grid: Vec<_> = features
+        .iter()
+        .map(|&feature| if feature { 0.0 } else { FAR })
+        .collect();
+    let n = width.max(height);
+    let (mut f, mut d) = (vec![0.0; n], vec![0.0; n]);
+    let (mut v, mut z) = (vec![0; n], vec![0.0; n + 1]);
+
+    for x in 0..width {
+        for y in 0..height {
+            f[y] = grid[y * width + x];
+        }
+        edt_1d(&f[..height], &mut d[..height], &mut v, &mut z);
+        for y in 0..height {
+            grid[y * width + x] = d[y];
+        }
+    }
+    for row in grid.chunks_exact_mut(width) {
+        f[..width].copy_from_slice(row);
+        edt_1d(&f[..width], &mut d[..width], &mut v, &mut z);
+        row.copy_from_slice(&d[..width]);
+    }
+    grid
+}
+
+/// Signed distance field of `coverage`, `spread` pixels larger on every side
+///
+/// The edge maps to 128 in the alpha channel, 255 is `spread` pixels inside and 0 as far outside.
+pub(crate) fn distance_field(coverage: &Coverage, spread: i32) -> Image {
+    let spread = spread.max(1);
+    let (width, height) = (coverage.width + 2 * spread, coverage.height + 2 * spread);
+    let (w, h) = (width as usize, height as usize);
+
+    let mut inside = vec![false; w * h];
+    for y in 0..coverage.height {
+        for x in 0..coverage.width {
+            let alpha = coverage.alpha[(y * coverage.width + x) as usize];
+            inside[(y + spread) as usize * w + (x + spread) as usize] = alpha >= 128;
+        }
+    }
+    let outside: Vec<_> = inside.iter().map(|inside| !inside).collect();
+    let to_inside = edt(&inside, w, h);
+    let to_outside = edt(&outside, w, h);
+
+    let pixels = (0..w * h)
+        .map(|i| {
+            // Distances are to pixel centers, the edge sits half a pixel before them
+            let distance = if inside[i] {
+                0.5 - to_outside[i].sqrt()
+            } else {
+                to_inside[i].sqrt() - 0.5
+            };
+            let value = (0.5 - distance / (2.0 * spread as f64)).clamp(0.0, 1.0);
+            Color::new(255, 255, 255, (value * 255.0).round() as u8)
+        })
+        .collect();
+    Image::from_colors(width, height, pixels).expect("size matches the pixel count")
+}
+
+/// Pack the distance field of every glyph, returning the atlas and glyphs pointing into it
+pub(crate) fn sdf_atlas(
+    glyphs: &[Glyph],
+    coverage: &[Coverage],
+    spread: i32,
+) -> Result<(Image, Vec<Glyph>), FontError> {
+    let mut builder = AtlasBuilder::new().padding(1);
+    for (i, coverage) in coverage.iter().enumerate() {
+        if coverage.width > 0 && coverage.height > 0 {
+            builder.add(i.to_string(), distance_field(coverage, spread));
+        }
+    }
+    let atlas = builder.build()?;
+    let glyphs = glyphs
+        .iter()
+        .enumerate()
+        .map(|(i, glyph)| match atlas.region(&i.to_string()) {
+            Some(region) => Glyph {
+                region,
+                offset: glyph.offset - Vector2::new(spread as f32, spread as f32),
+                ..*glyph
+            },
+            None => Glyph {
+                region: Rectangle::new(0.0, 0.0, 0.0, 0.0),
+                ..*glyph
+            },
+        })
+        .collect();
+    Ok((atlas.image().clone(), glyphs))
+}
+
+struct SdfShader {
+    shader: Shader,
+    outline_width: c_int,
+    outline_color: c_int,
+    glow_width: c_int,
+    glow_color: c_int,
+}
+
+thread_local! {
+    /// Loaded on first use, unloaded with the window
+    static SDF_SHADER: RefCell<Option<SdfShader>> = const { RefCell::new(None) };
+}
+
+impl SdfShader {
+    fn load() -> Self {
+        let version = unsafe { rl_get_version() };
+        let source = if version == GlVersion::OpenGl33 as c_int
+            || version == GlVersion::OpenGl43 as c_int
+        {
+            SDF_FS_330.to_owned()
+        } else {
+            let header = if version == GlVersion::OpenGl21 as c_int {
+                "#version 120\n"
+            } else {
+                "#version 100\n#extension GL_OES_standard_derivatives : enable\nprecision mediump float;\n"
+            };
+            std::ffi::CString::new(format!("{header}{SDF_FS_100}"))
+                .expect("shader source has no nul bytes")
+        };
+
+        let shader = unsafe { load_shader_from_memory(std::ptr::null(), source.as_ptr()) };
+        let location = |name: &CStr| unsafe { get_shader_location(shader.clone(), name.as_ptr()) };
+        Self {
+            outline_width: location(c"outlineWidth"),
+            outline_color: location(c"outlineColor"),
+            glow_width: location(c"glowWidth"),
+            glow_color: location(c"glowColor"),
+            shader,
+        }
+    }
+
+    fn set_float(&self, location: c_int, value: f32) {
+        unsafe {
+            set_shader_value(
+                self.shader.clone(),
+                location,
+                (&value as *const f32).cast(),
+                ShaderUniformDataType::Float as c_int,
+            );
+        }
+    }
+
+    fn set_color(&self, location: c_int, color: Color) {
+        let value = [color.r, color.g, color.b, color.a].map(|c| c as f32 / 255.0);
+        unsafe {
+            set_shader_value(
+                self.shader.clone(),
+                location,
+                value.as_ptr().cast(),
+                ShaderUniformDataType::Vec4 as c_int,
+            );
+        }
+    }
+}
+
+/// Run `draw` with the SDF shader bound when `font` needs it
+pub(crate) fn with_font_shader(font: &Font, effects: &SdfEffects, draw: impl FnOnce()) {
+    let Some(spread) = font.sdf_spread() else {
+        draw();
+        return;
+    };
+    SDF_SHADER.with_borrow_mut(|shader| {
+        let shader = shader.get_or_insert_with(SdfShader::load);
+        // Pixels to the alpha units the field is stored in
+        let scale = 1.0 / (2.0 * spread as f32);
+        shader.set_float(shader.outline_width, effects.outline_width * scale);
+        shader.set_color(shader.outline_color, effects.outline_color);
+        shader.set_float(shader.glow_width, effects.glow_width * scale);
+        shader.set_color(shader.glow_color, effects.glow_color);
+        unsafe { begin_shader_mode(shader.shader.clone()) };
+        draw();
+        unsafe { end_shader_mode() };
+    });
+}
+
+/// Free the SDF shader, must run before the GL context goes away
+pub(crate) fn unload_sdf_shader() {
+    if let Some(shader) = SDF_SHADER.take() {
+        unsafe { unload_shader(shader.shader) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: i32) -> Coverage {
+        Coverage {
+            width: size,
+            height: size,
+            alpha: vec![255; (size * size) as usize],
+        }
+    }
+
+    #[test]
+    fn distance_field_of_a_square() {
+        let field = distance_field(&square(10), 4);
+        assert_eq!((field.width(), field.height()), (18, 18));
+        let alpha = |x, y| field.get_pixel(x, y).unwrap().a;
+
+        assert_eq!(alpha(9, 9), 255);
+        // Either side of the left edge
+        assert_eq!(alpha(4, 9), 143);
+        assert_eq!(alpha(3, 9), 112);
+        assert_eq!(alpha(0, 0), 0);
+        // Falls off evenly away from the edge
+        let row: Vec<_> = (0..9).map(|x| alpha(x, 9)).collect();
+        assert!(row.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn packs_glyphs_with_spread() {
+        let glyph = |codepoint| Glyph {
+            codepoint,
+            region: Rectangle::new(0.0, 0.0, 10.0, 10.0),
+            offset: Vector2::new(1.0, 2.0),
+            advance: 11.0,
+        };
+        let empty = Coverage {
+            width: 0,
+            height: 0,
+            alpha: Vec::new(),
+        };
+        let (atlas, glyphs) =
+            sdf_atlas(&[glyph('a'), glyph(' ')], &[square(10), empty], 4).unwrap();
+        assert!(atlas.width() >= 18 && atlas.height() >= 18);
+        assert_eq!(glyphs[0].region.size(), Vector2::new(18.0, 18.0));
+        assert_eq!(glyphs[0].offset, Vector2::new(-3.0, -2.0));
+        assert_eq!(glyphs[0].advance, 11.0);
+        assert_eq!(glyphs[1].region.width, 0.0);
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use super::pak::{Pak, entry_name};
+
+pub use rustyray_macros::embed_dir;
+
+/// Prefix of keys loaded from blobs registered with [crate::core::assets::AssetManager::embed]
+pub const EMBEDDED_SCHEME: &str = "embedded://";
+
+pub type ReadFuture<'a> = Pin<Box<dyn Future<Output = io::Result<Vec<u8>>> + Send + 'a>>;
+
+/// Somewhere asset bytes can be read from, mounted on a [Vfs]
+///
+/// Reads of missing files must fail with [io::ErrorKind::NotFound] so lower priority sources
+/// get a chance to serve them.
+pub trait AssetSource: Debug + Send + Sync + 'static {
+    fn read<'a>(&'a self, path: &'a Path) -> ReadFuture<'a>;
+
+    /// Whether [AssetSource::read] would find `path` instead of failing with `NotFound`
+    fn contains(&self, path: &Path) -> bool;
+
+    /// Last modification time of `path`, sources returning `None` are never hot reloaded
+    fn modified(&self, _path: &Path) -> Option<SystemTime> {
+        None
+    }
+}
+
+pub(crate) fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} not found", path.display()),
+    )
+}
+
+pub(crate) fn embedded_name(path: &Path) -> Option<&str> {
+    path.to_str()?.strip_prefix(EMBEDDED_SCHEME)
+}
+
+/// Files on disk, relative to `root`
+#[derive(Debug, Clone, Default)]
+pub struct DiskSource {
+    root: PathBuf,
+}
+
+impl DiskSource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl AssetSource for DiskSource {
+    fn read<'a>(&'a self, path: &'a Path) -> ReadFuture<'a> {
+        Box::pin(async_fs::read(self.root.join(path)))
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.root.join(path).exists()
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(self.root.join(path))
+            .and_then(|meta| meta.modified())
+            .ok()
+    }
+}
+
+/// Files held in memory, useful for tests and generated content
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    files: HashMap<String, Arc<[u8]>>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, path: impl AsRef<Path>, bytes: impl Into<Arc<[u8]>>) {
+        if let Ok(name) = entry_name(path.as_ref()) {
+            self.files.insert(name, bytes.into());
+        }
+    }
+
+    pub fn with_file(mut self, path: impl AsRef<Path>, bytes: impl Into<Arc<[u8]>>) -> Self {
+        self.insert(path, bytes);
+        self
+    }
+
+    pub fn remove(&mut self, path: impl AsRef<Path>) -> Option<Arc<[u8]>> {
+        self.files.remove(&entry_name(path.as_ref()).ok()?)
+    }
+}
+
+impl AssetSource for MemorySource {
+    fn read<'a>(&'a self, path: &'a Path) -> ReadFuture<'a> {
+        let bytes = entry_name(path)
+            .ok()
+            .and_then(|name| self.files.get(&name))
+            .map(|bytes| bytes.to_vec())
+            .ok_or_else(|| not_found(path));
+        Box::pin(std::future::ready(bytes))
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        entry_name(path).is_ok_and(|name| self.files.contains_key(&name))
+    }
+}
+
+impl AssetSource for Pak {
+    fn read<'a>(&'a self, path: &'a Path) -> ReadFuture<'a> {
+        Box::pin(async move { Ok(Pak::read(self, path).await?) })
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        Pak::contains(self, path)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Mount {
+    source: Arc<dyn AssetSource>,
+    priority: i32,
+}
+
+/// Overlay of [AssetSource]s, higher priorities are searched first
+///
+/// Starts with a [DiskSource] relative to the working directory at priority `0`, mount a mod
+/// directory at a higher priority to override some of the base files:
+///
+/// ```no_run
+/// use rustyray::prelude::*;
+///
+/// let mut window = WindowBuilder::new(800, 600, "Example").build().unwrap();
+/// window.assets.mount(DiskSource::new("mods/hd_textures"), 10);
+/// ```
+///
+/// Keys starting with [EMBEDDED_SCHEME] never reach the mounted sources.
+#[derive(Debug, Clone)]
+pub struct Vfs {
+    mounts: Vec<Mount>,
+    embedded: HashMap<String, &'static [u8]>,
+}
+
+impl Default for Vfs {
+    fn default() -> Self {
+        let mut vfs = Self::empty();
+        vfs.mount(DiskSource::default(), 0);
+        vfs
+    }
+}
+
+impl Vfs {
+    /// A [Vfs] without any mounted source
+    pub fn empty() -> Self {
+        Self {
+            mounts: Vec::new(),
+            embedded: HashMap::new(),
+        }
+    }
+
+    /// Mount `source`, a source mounted later wins over one with the same priority
+    pub fn mount(&mut self, source: impl AssetSource, priority: i32) {
+        self.mount_arc(Arc::new(source), priority);
+    }
+
+    pub fn mount_arc(&mut self, source: Arc<dyn AssetSource>, priority: i32) {
+        let index = self
+            .mounts
+            .iter()
+            .position(|mount| mount.priority <= priority)
+            .unwrap_or(self.mounts.len());
+        self.mounts.insert(index, Mount { source, priority });
+    }
+
+    pub fn unmount_all(&mut self) {
+        self.mounts.clear();
+    }
+
+    pub fn embed(&mut self, name: impl Into<String>, bytes: &'static [u8]) {
+        self.embedded.insert(name.into(), bytes);
+    }
+
+    pub async fn read(&self, path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+        let path = path.as_ref();
+        if let Some(name) = embedded_name(path) {
+            return self
+                .embedded
+                .get(name)
+                .map(|bytes| bytes.to_vec())
+                .ok_or_else(|| not_found(path));
+        }
+
+        for mount in &self.mounts {
+            match mount.source.read(path).await {
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                result => return result,
+            }
+        }
+        Err(not_found(path))
+    }
+
+    pub fn modified(&self, path: impl AsRef<Path>) -> Option<SystemTime> {
+        let path = path.as_ref();
+        if embedded_name(path).is_some() {
+            return None;
+        }
+        // Only the mount `read` serves the file from, a shadowed copy is never loaded
+        self.mounts
+            .iter()
+            .find(|mount| mount.source.contains(path))?
+            .source
+            .modified(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::future::block_on;
+
+    fn read(vfs: &Vfs, path: &str) -> io::Result<Vec<u8>> {
+        block_on(vfs.read(path))
+    }
+
+    #[test]
+    fn higher_priority_overrides() {
+        let mut vfs = Vfs::empty();
+        vfs.mount(
+            MemorySource::new()
+                .with_file("a.txt", b"base a".as_slice())
+                .with_file("b.txt", b"base b".as_slice()),
+            0,
+        );
+        vfs.mount(
+            MemorySource::new().with_file("./a.txt", b"mod a".as_slice()),
+            10,
+        );
+
+        assert_eq!(read(&vfs, "a.txt").unwrap(), b"mod a");
+        assert_eq!(read(&vfs, "b.txt").unwrap(), b"base b");
+        assert_eq!(
+            read(&vfs, "c.txt").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn later_mount_wins_ties() {
+        let mut vfs = Vfs::empty();
+        vfs.mount(
+            MemorySource::new().with_file("a.txt", b"first".as_slice()),
+            0,
+        );
+        vfs.mount(
+            MemorySource::new().with_file("a.txt", b"second".as_slice()),
+            0,
+        );
+        vfs.mount(
+            MemorySource::new().with_file("a.txt", b"low".as_slice()),
+            -1,
+        );
+
+        assert_eq!(read(&vfs, "a.txt").unwrap(), b"second");
+    }
+
+    #[test]
+    fn embedded_files_bypass_mounts() {
+        let mut vfs = Vfs::empty();
+        vfs.mount(
+            MemorySource::new().with_file("embedded://a.txt", b"mounted".as_slice()),
+            0,
+        );
+        vfs.embed("a.txt", b"embedded");
+
+        assert_eq!(read(&vfs, "embedded://a.txt").unwrap(), b"embedded");
+        assert!(read(&vfs, "embedded://b.txt").is_err());
+        assert!(vfs.modified("embedded://a.txt").is_none());
+    }
+
+    #[test]
+    fn disk_source_reads_relative_to_root() {
+        let root = std::env::temp_dir().join(format!("rustyray-{}-vfs", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("a.txt"), b"disk").unwrap();
+
+        let mut vfs = Vfs::empty();
+        vfs.mount(DiskSource::new(&root), 0);
+        assert_eq!(read(&vfs, "a.txt").unwrap(), b"disk");
+        assert!(vfs.modified("a.txt").is_some());
+        assert!(vfs.modified("missing.txt").is_none());
+
+        vfs.mount(
+            MemorySource::new().with_file("a.txt", b"memory".as_slice()),
+            10,
+        );
+        assert_eq!(read(&vfs, "a.txt").unwrap(), b"memory");
+        assert!(vfs.modified("a.txt").is_none());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -8,7 +8,9 @@ use crate::prelude::{DrawingExt, GamepadAxis, GamepadButton, HasAssetManager, Te
 use super::{
     assets::AssetManager,
     consts::{ConfigFlag, KeyboardKey, MouseButton},
+    image::{Image, ImageError},
     math::{Vector2, Vector2i},
+    text,
 };
 
 #[derive(Debug)]
@@ -424,6 +426,12 @@ impl Window {
         unsafe { ffi::get_time() }
     }
 
+    /// Read the backbuffer into a CPU image, top row first
+    pub fn capture_screen(&self) -> Result<Image, ImageError> {
+        // raylib already flips the framebuffer rows while reading them back
+        Image::from_ray(unsafe { ffi::load_image_from_screen() })
+    }
+
     #[inline]
     pub fn window_size(&mut self, width: i32, height: i32) {
         unsafe {
@@ -542,6 +550,7 @@ impl Drop for Window {
     fn drop(&mut self) {
         unsafe {
             ManuallyDrop::drop(&mut self.assets);
+            text::unload_sdf_shader();
 
             if ffi::is_audio_device_ready() {
                 ffi::close_audio_device();
@@ -0,0 +1,167 @@
+use std::any::Any;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::JoinHandle;
+
+type Work = Box<dyn FnOnce() + Send>;
+
+/// Threads running the CPU-side part of asset loads
+pub(crate) struct WorkerPool {
+    work: Option<Sender<Work>>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+/// A [WorkerPool::spawn] future panicked instead of finishing
+#[derive(Debug, thiserror::Error)]
+#[error("asset loader panicked: {0}")]
+pub(crate) struct Panicked(String);
+
+impl Panicked {
+    fn new(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload.downcast_ref::<&str>().map_or_else(
+                || String::from("unknown panic"),
+                |message| message.to_string(),
+            ),
+        };
+        Self(message)
+    }
+}
+
+const MAX_WORKERS: usize = 4;
+
+impl WorkerPool {
+    pub(crate) fn new() -> Self {
+        let count = std::thread::available_parallelism()
+            .map(|count| count.get().clamp(1, MAX_WORKERS))
+            .unwrap_or(1);
+        let (work, rx) = mpsc::channel::<Work>();
+        let rx = Arc::new(Mutex::new(rx));
+
+        let threads = (0..count)
+            .map(|index| {
+                let rx = Arc::clone(&rx);
+                std::thread::Builder::new()
+                    .name(format!("rustyray-assets-{index}"))
+                    .spawn(move || run(&rx))
+                    .expect("failed to spawn asset worker thread")
+            })
+            .collect();
+
+        Self {
+            work: Some(work),
+            threads,
+        }
+    }
+
+    /// Run `future` to completion on a worker thread
+    ///
+    /// The future is created on the worker, so only its inputs and output have to be [Send].
+    /// A panic is caught and reported as [Panicked] so the worker keeps running.
+    pub(crate) fn spawn<F, Fut>(&self, make_future: F) -> Task<Result<Fut::Output, Panicked>>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future,
+        Fut::Output: Send + 'static,
+    {
+        let shared = Arc::new(Shared {
+            result: Mutex::new(None),
+            waker: Mutex::new(None),
+        });
+
+        let task = Task {
+            shared: Arc::clone(&shared),
+        };
+        let work: Work = Box::new(move || {
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                futures_lite::future::block_on(make_future())
+            }))
+            .map_err(Panicked::new);
+            *shared.result.lock().unwrap() = Some(result);
+            if let Some(waker) = shared.waker.lock().unwrap().take() {
+                waker.wake();
+            }
+        });
+        if let Some(sender) = &self.work {
+            let _ = sender.send(work);
+        }
+
+        task
+    }
+}
+
+fn run(rx: &Mutex<Receiver<Work>>) {
+    loop {
+        let work = rx.lock().unwrap().recv();
+        match work {
+            Ok(work) => work(),
+            Err(_) => return,
+        }
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        self.work.take();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+struct Shared<R> {
+    result: Mutex<Option<R>>,
+    waker: Mutex<Option<Waker>>,
+}
+
+/// Result of [WorkerPool::spawn], polled from the main thread
+pub(crate) struct Task<R> {
+    shared: Arc<Shared<R>>,
+}
+
+impl<R> Future for Task<R> {
+    type Output = R;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Register the waker before checking so a result landing in between still wakes us
+        *self.shared.waker.lock().unwrap() = Some(cx.waker().clone());
+        match self.shared.result.lock().unwrap().take() {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Waker flagging a pending load to be polled on the next [super::assets::AssetManager::process_assets]
+#[derive(Debug)]
+pub(crate) struct Flag {
+    woken: AtomicBool,
+}
+
+impl Flag {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            woken: AtomicBool::new(true),
+        })
+    }
+
+    pub(crate) fn take(&self) -> bool {
+        self.woken.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl Wake for Flag {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+    }
+}
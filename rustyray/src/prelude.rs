@@ -5,5 +5,9 @@ pub use crate::core::consts::*;
 pub use crate::core::drawing::*;
 pub use crate::core::image::*;
 pub use crate::core::math::*;
+pub use crate::core::pak::*;
+pub use crate::core::sprite::*;
+pub use crate::core::text::*;
+pub use crate::core::vfs::*;
 pub use crate::core::window::*;
 pub use crate::core::*;